@@ -155,12 +155,23 @@ impl GodotClass {
         self.properties.iter().any(|p| p.getter == name)
     }
 
-    /// Whether there is a snake_case module containing related symbols (nested types in C++)
+    /// Whether there is a snake_case module containing related symbols (nested types in C++,
+    /// and typed notification constants)
     pub fn has_related_module(&self) -> bool {
-        !self.enums.is_empty()
+        !self.enums.is_empty() || self.has_notifications()
+    }
+
+    /// Whether the class declares `NOTIFICATION_*` constants
+    pub fn has_notifications(&self) -> bool {
+        self.constants
+            .keys()
+            .any(|name| name.starts_with(NOTIFICATION_PREFIX))
     }
 }
 
+/// Prefix of the constants that are also generated as typed notification constants
+pub(crate) const NOTIFICATION_PREFIX: &str = "NOTIFICATION_";
+
 pub type ConstantName = String;
 pub type ConstantValue = i64;
 
@@ -95,6 +95,62 @@ pub(crate) fn generate_class_constants(class: &GodotClass) -> TokenStream {
     }
 }
 
+pub(crate) fn generate_notifications(class: &GodotClass) -> TokenStream {
+    let mut notifications: Vec<(&ConstantName, &ConstantValue)> = class
+        .constants
+        .iter()
+        .filter(|(name, _)| name.starts_with(NOTIFICATION_PREFIX))
+        .collect();
+    notifications.sort_by(constant_sorter);
+
+    let consts = notifications.iter().map(|(name, value)| {
+        let name = format_ident!("{}", name.trim_start_matches(NOTIFICATION_PREFIX));
+        quote! {
+            pub const #name: Notification = Notification(#value);
+        }
+    });
+
+    let doc = format!(
+        "Typed `NOTIFICATION_*` constants of [`{}`], which can be compared with the notifications \
+        received by `LifecycleHooks::on_notification`.",
+        class.name
+    );
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct Notification(pub i64);
+
+        impl Notification {
+            #(#consts)*
+        }
+        impl From<i64> for Notification {
+            #[inline]
+            fn from(v: i64) -> Self {
+                Self(v)
+            }
+        }
+        impl From<Notification> for i64 {
+            #[inline]
+            fn from(v: Notification) -> Self {
+                v.0
+            }
+        }
+        impl From<Notification> for gdnative_core::export::Notification {
+            #[inline]
+            fn from(v: Notification) -> Self {
+                Self(v.0)
+            }
+        }
+        impl PartialEq<Notification> for gdnative_core::export::Notification {
+            #[inline]
+            fn eq(&self, other: &Notification) -> bool {
+                self.0 == other.0
+            }
+        }
+    }
+}
+
 pub(crate) fn generate_enums(class: &GodotClass) -> TokenStream {
     // TODO: check whether the start of the variant name is equal to the end of the enum name and if so, don't repeat it.
     // For example ImageFormat::Rgb8 instead of ImageFormat::FormatRgb8.
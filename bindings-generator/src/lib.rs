@@ -54,6 +54,7 @@ pub type GeneratorResult<T = ()> = Result<T, io::Error>;
 pub struct BindingResult<'a> {
     pub class_bindings: Vec<(&'a GodotClass, TokenStream)>,
     pub icalls: TokenStream,
+    pub virtuals: TokenStream,
 }
 
 pub fn generate_bindings<'a>(api: &'a Api, docs: Option<&GodotXmlDocs>) -> BindingResult<'a> {
@@ -75,9 +76,12 @@ pub fn generate_bindings<'a>(api: &'a Api, docs: Option<&GodotXmlDocs>) -> Bindi
         .map(|(name, sig)| generate_icall(name, sig))
         .collect();
 
+    let virtuals = generate_virtuals_module(api);
+
     BindingResult {
         class_bindings,
         icalls,
+        virtuals,
     }
 }
 
@@ -103,6 +107,12 @@ fn generate_class_bindings(
 
         let enums = generate_enums(class);
 
+        let notifications = if class.has_notifications() {
+            generate_notifications(class)
+        } else {
+            Default::default()
+        };
+
         let constants = if !class.constants.is_empty() {
             generate_class_constants(class)
         } else {
@@ -115,6 +125,7 @@ fn generate_class_bindings(
             #module_doc
             #class_struct
             #enums
+            #notifications
             #constants
             #class_impl
         }
@@ -134,6 +145,8 @@ fn generate_class_bindings(
 
         let sub_class = generate_sub_class_impls(api, class);
 
+        let virtual_trait = generate_virtual_trait(api, class);
+
         // Instantiable
         let instantiable = if class.instantiable {
             generate_instantiable_impl(class)
@@ -152,6 +165,7 @@ fn generate_class_bindings(
             #free_impl
             #base_class
             #sub_class
+            #virtual_trait
             #instantiable
             #send_sync
         }
@@ -245,6 +259,12 @@ pub(crate) mod test_prelude {
             write!(buffer, "{}", code).unwrap();
             validate_and_clear_buffer!(buffer);
 
+            if class.has_notifications() {
+                let code = generate_notifications(&class);
+                write!(buffer, "{}", code).unwrap();
+                validate_and_clear_buffer!(buffer);
+            }
+
             if !class.constants.is_empty() {
                 let code = generate_class_constants(&class);
                 write!(buffer, "{}", code).unwrap();
@@ -270,6 +290,10 @@ pub(crate) mod test_prelude {
                 validate_and_clear_buffer!(buffer);
             }
 
+            let code = generate_virtual_trait(&api, &class);
+            write!(buffer, "{}", code).unwrap();
+            validate_and_clear_buffer!(buffer);
+
             // Instantiable
             if class.instantiable {
                 let code = generate_instantiable_impl(&class);
@@ -288,5 +312,9 @@ pub(crate) mod test_prelude {
                 validate_and_clear_buffer!(buffer);
             }
         }
+
+        let code = generate_virtuals_module(&api);
+        write!(buffer, "{}", code).unwrap();
+        validate_and_clear_buffer!(buffer);
     }
 }
@@ -232,3 +232,196 @@ pub fn generate_gdnative_library_singleton_getter(class: &GodotClass) -> TokenSt
         }
     }
 }
+
+/// Returns the virtual methods declared by `class`, excluding the ones already declared by one
+/// of its base classes, and the ones with varargs.
+fn own_virtual_methods<'a>(
+    api: &'a Api,
+    class: &'a GodotClass,
+) -> impl Iterator<Item = &'a GodotMethod> + 'a {
+    class.methods.iter().filter(move |method| {
+        if !method.is_virtual || method.has_varargs {
+            return false;
+        }
+
+        let mut base = class.base_class(api);
+        while let Some(base_class) = base {
+            if base_class
+                .methods
+                .iter()
+                .any(|m| m.is_virtual && m.name == method.name)
+            {
+                return false;
+            }
+            base = base_class.base_class(api);
+        }
+
+        true
+    })
+}
+
+/// Type used for `ty` in the signatures of virtual methods. Enums are passed as integers.
+fn virtual_method_type(ty: &Ty) -> syn::Type {
+    match ty {
+        Ty::Enum(_) | Ty::Result | Ty::VariantType | Ty::VariantOperator | Ty::Vector3Axis => {
+            syn::parse_quote! { i64 }
+        }
+        _ => ty.to_rust(),
+    }
+}
+
+/// Generates the `{Class}Virtual` trait, used by `#[method(virtual)]` to check overrides of the
+/// virtual methods introduced by `class`.
+pub fn generate_virtual_trait(api: &Api, class: &GodotClass) -> TokenStream {
+    let methods: Vec<_> = own_virtual_methods(api, class).collect();
+    if methods.is_empty() {
+        return TokenStream::new();
+    }
+
+    let class_name = format_ident!("{}", class.name);
+    let trait_name = format_ident!("{}Virtual", class.name);
+
+    let trait_doc = format!(
+        "Virtual methods introduced by [`{0}`], which scripts extending `{0}` or one of its \
+        subclasses can override.\n\n\
+        This trait is implemented for `{0}` and all its subclasses. It is used by \
+        `#[method(virtual)]` to check the names and signatures of overrides at compile time, \
+        and is not meant to be used directly.",
+        class.name
+    );
+
+    let methods = methods.into_iter().map(|method| {
+        let name = crate::rust_safe_name(&method.name);
+
+        let signature = method
+            .arguments
+            .iter()
+            .map(|arg| format!("{}: {}", arg.name, arg.ty))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let doc = format!("`{}({}) -> {}`", method.name, signature, method.return_type);
+
+        let params: Vec<_> = (0..method.arguments.len())
+            .map(|i| format_ident!("A{}", i))
+            .collect();
+        let arg_types = method
+            .arguments
+            .iter()
+            .map(|arg| virtual_method_type(&arg.get_type()));
+        let return_type = virtual_method_type(&method.get_return_type());
+
+        quote! {
+            #[doc = #doc]
+            #[inline]
+            fn #name<#(#params,)* R>(_signature: std::marker::PhantomData<fn(#(#params),*) -> R>)
+            where
+                #(#params: gdnative_core::export::VirtualType<#arg_types>,)*
+                R: gdnative_core::export::VirtualType<#return_type>,
+            {
+            }
+        }
+    });
+
+    quote! {
+        #[doc = #trait_doc]
+        pub trait #trait_name {
+            #(#methods)*
+        }
+
+        impl<T: SubClass<#class_name>> #trait_name for T {}
+    }
+}
+
+/// Generates the `virtuals` module, which re-exports the `{Class}Virtual` traits of all classes.
+pub fn generate_virtuals_module(api: &Api) -> TokenStream {
+    let uses = api
+        .classes
+        .iter()
+        .filter(|class| own_virtual_methods(api, class).next().is_some())
+        .map(|class| {
+            let module = format_ident!("{}", class.module());
+            let trait_name = format_ident!("{}Virtual", class.name);
+            quote! {
+                pub use crate::generated::#module::#trait_name;
+            }
+        });
+
+    quote! {
+        /// Traits describing the virtual methods of engine classes, used by `#[method(virtual)]`
+        /// to check overrides at compile time.
+        pub mod virtuals {
+            #(#uses)*
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const API_JSON: &str = r#"[
+        {
+            "name": "Object", "base_class": "", "api_type": "core", "singleton": false,
+            "is_reference": false, "instanciable": true,
+            "properties": [], "enums": [], "constants": {},
+            "methods": [
+                {
+                    "name": "_notification", "return_type": "void",
+                    "is_editor": false, "is_noscript": false, "is_const": false,
+                    "is_reverse": false, "is_virtual": true, "has_varargs": false,
+                    "arguments": [
+                        { "name": "what", "type": "int", "has_default_value": false, "default_value": "" }
+                    ]
+                }
+            ]
+        },
+        {
+            "name": "Node", "base_class": "Object", "api_type": "core", "singleton": false,
+            "is_reference": false, "instanciable": true,
+            "properties": [], "enums": [], "constants": {},
+            "methods": [
+                {
+                    "name": "_notification", "return_type": "void",
+                    "is_editor": false, "is_noscript": false, "is_const": false,
+                    "is_reverse": false, "is_virtual": true, "has_varargs": false,
+                    "arguments": [
+                        { "name": "what", "type": "int", "has_default_value": false, "default_value": "" }
+                    ]
+                },
+                {
+                    "name": "_input", "return_type": "void",
+                    "is_editor": false, "is_noscript": false, "is_const": false,
+                    "is_reverse": false, "is_virtual": true, "has_varargs": false,
+                    "arguments": [
+                        { "name": "event", "type": "InputEvent", "has_default_value": false, "default_value": "" }
+                    ]
+                },
+                {
+                    "name": "_get_configuration_warning", "return_type": "String",
+                    "is_editor": false, "is_noscript": false, "is_const": false,
+                    "is_reverse": false, "is_virtual": true, "has_varargs": false,
+                    "arguments": []
+                }
+            ]
+        }
+    ]"#;
+
+    #[test]
+    fn virtual_trait_skips_inherited_methods() {
+        let api = Api::new(API_JSON);
+        let node = api.find_class("Node").unwrap();
+
+        let names: Vec<_> = own_virtual_methods(&api, node)
+            .map(|method| method.name.as_str())
+            .collect();
+        assert_eq!(names, ["_input", "_get_configuration_warning"]);
+
+        let code = generate_virtual_trait(&api, node);
+        let file: syn::File = syn::parse2(code).unwrap();
+        assert_eq!(file.items.len(), 2);
+
+        let code = generate_virtuals_module(&api).to_string();
+        assert!(code.contains("ObjectVirtual"));
+        assert!(code.contains("NodeVirtual"));
+    }
+}
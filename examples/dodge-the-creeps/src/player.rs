@@ -26,14 +26,14 @@ impl Player {
         }
     }
 
-    #[method]
+    #[method(virtual)]
     fn _ready(&mut self, #[base] owner: &Area2D) {
         let viewport = owner.get_viewport_rect();
         self.screen_size = viewport.size;
         owner.hide();
     }
 
-    #[method]
+    #[method(virtual)]
     fn _process(&mut self, #[base] owner: &Area2D, delta: f32) {
         let animated_sprite = unsafe {
             owner
@@ -22,6 +22,8 @@ fn main() {
         let mut output = BufWriter::new(File::create(&generated_rs).unwrap());
 
         generate(&out_path, &mut output, &binding_res);
+
+        write!(output, "{}", binding_res.virtuals).unwrap();
     }
 
     {
@@ -1,11 +1,20 @@
 use crate::*;
 use std::borrow::Cow;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::default::Default;
 use std::fmt;
-use std::hash::Hash;
+use std::hash::{BuildHasher, Hash};
 use std::mem::{forget, transmute};
+use std::num::{
+    NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU16, NonZeroU32,
+    NonZeroU64, NonZeroU8, NonZeroUsize,
+};
 use std::ptr;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use indexmap::IndexMap;
 
 use crate::core_types::*;
 use crate::object::ownership::*;
@@ -699,6 +708,19 @@ godot_test!(
 /// - `PhantomData<T>` is represented as `Nil`.
 /// - `&[T]` and `Vec<T>` are represented as `VariantArray`s. `FromVariant` is only implemented
 /// for `Vec<T>`.
+/// - `[T; N]`, `VecDeque<T>`, `HashSet<T>` and `BTreeSet<T>` are represented as `VariantArray`s.
+/// Sets are converted in ascending order.
+/// - `HashMap<K, V>`, `BTreeMap<K, V>` and `IndexMap<K, V>` are represented as `Dictionary`s.
+/// - `Box<T>`, `Rc<T>`, `Arc<T>` and `Cow<T>` are represented as the inner value.
+/// - `char` is represented as a `GodotString` of length 1.
+/// - `NonZeroI32` and the other `NonZero*` integers are represented as integers.
+/// - `Duration` is represented as a `f64` number of seconds.
+///
+/// Numeric sequences such as `Vec<u8>`, `Vec<f32>` or `[Vector3; N]` can be represented as the
+/// matching `PoolArray` type instead of `VariantArray`s with the functions of the
+/// [`as_pool_array`] module, e.g. as `#[variant(with = "gdnative::core_types::as_pool_array")]`
+/// on a derived field. This is considerably faster for large sequences. For other byte
+/// containers, the [`as_byte_array`] module can be used in the same way.
 ///
 /// ## Deriving `ToVariant`
 ///
@@ -713,6 +735,50 @@ godot_test!(
 ///
 /// Behavior of the derive macros can be customized using attributes:
 ///
+/// ### Container attributes
+///
+/// - `#[variant(repr = "array")]`
+///
+/// Represent a struct with named fields as a `VariantArray` of its fields in declaration order
+/// (`[a, b, c]`), instead of a `Dictionary`. `repr = "dictionary"` is the default.
+///
+/// - `#[variant(tag = "type")]`
+///
+/// Represent an enum as an internally tagged `Dictionary`, where the name of the variant is
+/// stored under the given key, next to the fields of the variant
+/// (`{ "type": "Variant", "a": a, "b": b }`). Only unit and struct variants are supported.
+///
+/// - `#[variant(repr = "untagged")]`
+///
+/// Represent an enum as the content of the variant alone, with unit variants as `Nil`. When
+/// converting from `Variant`, each variant is tried in declaration order, and the first one
+/// that succeeds is returned.
+///
+/// - `#[variant(repr = "int")]`
+///
+/// Represent a fieldless enum as the integer value of its variants, using the explicit
+/// discriminants where present. `repr = "external"` is the default for enums.
+///
+/// - `#[variant(rename_all = "camelCase")]`
+///
+/// Rename the fields of a struct, or the variants of an enum, according to the given case
+/// convention. Supported conventions are `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`,
+/// `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case` and `SCREAMING-KEBAB-CASE`.
+///
+/// - `#[variant(deny_unknown_fields)]`
+///
+/// Return `FromVariantError::UnknownField` when converting from a `Dictionary` that contains
+/// keys other than the field names, instead of ignoring them. Cannot be combined with
+/// flattened fields.
+///
+/// - `#[variant(collect_errors)]`
+///
+/// Convert all fields when converting from `Variant`, instead of stopping at the first error,
+/// and return all errors found as `FromVariantError::Multiple`. Nested types only report all
+/// of their own errors if they also have this attribute. Use
+/// [`FromVariantError::all_errors`] or [`FromVariantError::to_compact_string`] to report
+/// the errors with their paths.
+///
 /// ### Field attributes
 ///
 /// - `#[variant(to_variant_with = "path::to::func")]`
@@ -743,6 +809,79 @@ godot_test!(
 /// - `#[variant(skip)]`
 ///
 /// Convenience attribute that sets `skip_to_variant` and `skip_from_variant`.
+///
+/// - `#[variant(rename = "name")]`
+///
+/// Use the given name as the key of a named field, instead of the field name. Takes precedence
+/// over `rename_all`.
+///
+/// - `#[variant(default)]`
+///
+/// Use `Default::default()` when converting from a `Dictionary` that does not contain the
+/// field. This allows adding fields to types that are loaded from older data.
+///
+/// - `#[variant(default = "path::to::func")]`
+///
+/// Call the given function to obtain the value of a missing field instead. The function's
+/// signature is expected to be `fn() -> T`. Also applies to fields skipped with
+/// `skip_from_variant`.
+///
+/// - `#[variant(flatten)]`
+///
+/// Insert the entries of the field, which must be represented as a `Dictionary`, into the
+/// `Dictionary` of the containing type, instead of nesting it under the field name. When
+/// converting from `Variant`, the field is converted from the whole containing `Dictionary`.
+/// A field that is represented as `nil`, like `None`, adds no entries. A flattened `Option` is
+/// converted from `Variant` as `None` when the `Dictionary` has no entries besides the ones of
+/// the containing type, so it cannot be combined with other flattened fields. Fields of types
+/// that are never represented as a `Dictionary`, like integers or `Vec`, are rejected at compile
+/// time. For other types, a field that is represented as anything else is reported as an error
+/// and adds no entries.
+///
+/// ### Examples
+///
+/// ```
+/// use gdnative::prelude::*;
+///
+/// #[derive(ToVariant, FromVariant)]
+/// #[variant(rename_all = "camelCase")]
+/// struct Settings {
+///     // Stored as "maxPlayers"
+///     max_players: i64,
+///     #[variant(rename = "map", default)]
+///     map_name: String,
+/// }
+///
+/// #[derive(ToVariant, FromVariant)]
+/// struct SaveFile {
+///     // "maxPlayers" and "map" are stored next to "slot"
+///     #[variant(flatten)]
+///     settings: Settings,
+///     // Missing in older save files
+///     #[variant(default = "first_slot")]
+///     slot: i64,
+/// }
+///
+/// fn first_slot() -> i64 {
+///     1
+/// }
+///
+/// #[derive(ToVariant, FromVariant)]
+/// #[variant(tag = "type", rename_all = "snake_case")]
+/// enum Shape {
+///     // { "type": "circle", "radius": 1.0 }
+///     Circle { radius: f64 },
+///     // { "type": "empty" }
+///     Empty,
+/// }
+///
+/// #[derive(ToVariant, FromVariant)]
+/// #[variant(repr = "int")]
+/// enum Team {
+///     Red = 1,
+///     Blue = 2,
+/// }
+/// ```
 pub trait ToVariant {
     fn to_variant(&self) -> Variant;
 }
@@ -899,11 +1038,28 @@ pub enum FromVariantError {
         index: usize,
         error: Box<FromVariantError>,
     },
+
+    /// Error indicating that a `Dictionary` contains a key that is not a field of the struct,
+    /// returned by derived implementations with `#[variant(deny_unknown_fields)]`.
+    UnknownField {
+        /// The unknown key
+        field_name: String,
+        /// Names of all expected fields known at compile time
+        expected: &'static [&'static str],
+    },
+
+    /// Multiple errors in the fields or items of a value, returned by derived implementations
+    /// with `#[variant(collect_errors)]`. Use [`FromVariantError::all_errors`] to list them
+    /// together with their paths.
+    Multiple(Vec<FromVariantError>),
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum VariantEnumRepr {
     ExternallyTagged,
+    InternallyTagged,
+    Untagged,
+    Int,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
@@ -913,12 +1069,153 @@ pub enum VariantStructRepr {
     Struct,
 }
 
+/// A segment of the path from a converted value to the part of it that caused a
+/// [`FromVariantError`], as returned by [`FromVariantError::path`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ErrorPathSegment {
+    /// A field of a struct, or a key of a `Dictionary`.
+    Field(&'static str),
+    /// An item of a collection.
+    Index(usize),
+    /// A variant of an enum. Written like a field, as in the default externally tagged
+    /// representation.
+    Variant(&'static str),
+}
+
+impl ErrorPathSegment {
+    /// Writes `path` in a compact form, e.g. `inventory[3].stats.hp`.
+    fn write_path(f: &mut impl fmt::Write, path: &[ErrorPathSegment]) -> fmt::Result {
+        for (index, segment) in path.iter().enumerate() {
+            match segment {
+                ErrorPathSegment::Field(name) | ErrorPathSegment::Variant(name) if index == 0 => {
+                    write!(f, "{}", name)?
+                }
+                segment => write!(f, "{}", segment)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ErrorPathSegment {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorPathSegment::Field(name) | ErrorPathSegment::Variant(name) => {
+                write!(f, ".{}", name)
+            }
+            ErrorPathSegment::Index(index) => write!(f, "[{}]", index),
+        }
+    }
+}
+
 impl FromVariantError {
     /// Returns a `FromVariantError` with a custom message.
     #[inline]
     pub fn custom<T: fmt::Display>(message: T) -> Self {
         FromVariantError::Custom(format!("{}", message))
     }
+
+    /// Returns the segment and the error nested in this error, if this is an error in a field,
+    /// an item or an enum variant.
+    fn nested(&self) -> Option<(ErrorPathSegment, &FromVariantError)> {
+        use FromVariantError as E;
+
+        match self {
+            E::InvalidField { field_name, error } => {
+                Some((ErrorPathSegment::Field(field_name), error))
+            }
+            E::InvalidItem { index, error } => Some((ErrorPathSegment::Index(*index), error)),
+            E::InvalidEnumVariant { variant, error } => {
+                Some((ErrorPathSegment::Variant(variant), error))
+            }
+            E::Multiple(errors) => errors.first().and_then(FromVariantError::nested),
+            _ => None,
+        }
+    }
+
+    /// Returns the path from the converted value to the part of it that caused the error, e.g.
+    /// `[Field("inventory"), Index(3), Field("stats"), Field("hp")]`. The path is empty if the
+    /// error is about the value itself.
+    ///
+    /// For [`FromVariantError::Multiple`], this is the path of the first error.
+    #[inline]
+    pub fn path(&self) -> Vec<ErrorPathSegment> {
+        let mut path = Vec::new();
+        let mut error = self;
+        while let Some((segment, nested)) = error.nested() {
+            path.push(segment);
+            error = nested;
+        }
+        path
+    }
+
+    /// Returns the error at the end of [`path`](Self::path), which describes what is wrong
+    /// with the value there.
+    #[inline]
+    pub fn innermost(&self) -> &FromVariantError {
+        let mut error = self;
+        while let Some((_, nested)) = error.nested() {
+            error = nested;
+        }
+        match error {
+            FromVariantError::Multiple(errors) if !errors.is_empty() => errors[0].innermost(),
+            error => error,
+        }
+    }
+
+    /// Returns all errors contained in this one with their paths. This is more than one only
+    /// for errors containing [`FromVariantError::Multiple`].
+    #[inline]
+    pub fn all_errors(&self) -> Vec<(Vec<ErrorPathSegment>, &FromVariantError)> {
+        fn collect<'a>(
+            error: &'a FromVariantError,
+            path: &mut Vec<ErrorPathSegment>,
+            all: &mut Vec<(Vec<ErrorPathSegment>, &'a FromVariantError)>,
+        ) {
+            match error {
+                FromVariantError::InvalidField { .. }
+                | FromVariantError::InvalidItem { .. }
+                | FromVariantError::InvalidEnumVariant { .. } => {
+                    let (segment, nested) = error.nested().expect("error should be nested");
+                    path.push(segment);
+                    collect(nested, path, all);
+                    path.pop();
+                }
+                FromVariantError::Multiple(errors) => {
+                    for error in errors {
+                        collect(error, path, all);
+                    }
+                }
+                error => all.push((path.clone(), error)),
+            }
+        }
+
+        let mut all = Vec::new();
+        collect(self, &mut Vec::new(), &mut all);
+        all
+    }
+
+    /// Returns a one-line description of all errors, each prefixed with its path, e.g.
+    /// `inventory[3].stats.hp: invalid variant type: expected I64, got Nil`.
+    #[inline]
+    pub fn to_compact_string(&self) -> String {
+        use std::fmt::Write;
+
+        // Writing to a String cannot fail
+        let mut compact = String::new();
+        for (index, (path, error)) in self.all_errors().into_iter().enumerate() {
+            if index > 0 {
+                compact.push_str("; ");
+            }
+            if !path.is_empty() {
+                let _ = ErrorPathSegment::write_path(&mut compact, &path);
+                compact.push_str(": ");
+            }
+            let _ = write!(compact, "{}", error);
+        }
+        compact
+    }
 }
 
 impl fmt::Display for FromVariantError {
@@ -1001,6 +1298,27 @@ impl fmt::Display for FromVariantError {
             E::InvalidItem { index, error } => {
                 write!(f, "invalid value for item at index {}: {}", index, error)
             }
+            E::Multiple(errors) => {
+                write!(f, "{} errors: ", errors.len())?;
+                for (index, error) in errors.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{}", error.to_compact_string())?;
+                }
+                Ok(())
+            }
+            E::UnknownField {
+                field_name,
+                expected,
+            } => {
+                write!(
+                    f,
+                    "unknown field {}, expected fields are: {}",
+                    field_name,
+                    expected.join(", ")
+                )
+            }
         }
     }
 }
@@ -1697,6 +2015,382 @@ impl<T: FromVariant + Eq + Hash> FromVariant for HashSet<T> {
     }
 }
 
+/// Converts the map to a `Dictionary`, wrapped in a `Variant`. The entries are inserted in
+/// key order.
+impl<K: ToVariant + ToVariantEq, V: ToVariant> ToVariant for BTreeMap<K, V> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        let dict = Dictionary::new();
+        for (key, value) in self {
+            dict.insert(key.to_variant(), value.to_variant());
+        }
+        dict.owned_to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a `Dictionary` and tries to convert it into a `BTreeMap`.
+impl<K: FromVariant + Ord, V: FromVariant> FromVariant for BTreeMap<K, V> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let dictionary = Dictionary::from_variant(variant)?;
+        let mut map = BTreeMap::new();
+        for (key, value) in dictionary.iter() {
+            map.insert(K::from_variant(&key)?, V::from_variant(&value)?);
+        }
+        Ok(map)
+    }
+}
+
+/// Converts the map to a `Dictionary`, wrapped in a `Variant`. The entries are inserted in
+/// the order of the map.
+impl<K: ToVariant + ToVariantEq, V: ToVariant, S> ToVariant for IndexMap<K, V, S> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        let dict = Dictionary::new();
+        for (key, value) in self {
+            dict.insert(key.to_variant(), value.to_variant());
+        }
+        dict.owned_to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a `Dictionary` and tries to convert it into an
+/// `IndexMap`, keeping the order of the dictionary.
+impl<K, V, S> FromVariant for IndexMap<K, V, S>
+where
+    K: FromVariant + Hash + Eq,
+    V: FromVariant,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let dictionary = Dictionary::from_variant(variant)?;
+        let len: usize = dictionary
+            .len()
+            .try_into()
+            .expect("Dictionary length should fit in usize");
+
+        let mut map = IndexMap::with_capacity_and_hasher(len, S::default());
+        for (key, value) in dictionary.iter() {
+            map.insert(K::from_variant(&key)?, V::from_variant(&value)?);
+        }
+        Ok(map)
+    }
+}
+
+/// Converts the set to a `VariantArray` in ascending order, wrapped in a `Variant`.
+impl<T: ToVariant> ToVariant for BTreeSet<T> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        let array = VariantArray::new();
+        for value in self {
+            array.push(value.to_variant());
+        }
+        array.owned_to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a `VariantArray` and tries to convert it into a `BTreeSet`.
+impl<T: FromVariant + Ord> FromVariant for BTreeSet<T> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Vec::<T>::from_variant(variant).map(|vec| vec.into_iter().collect())
+    }
+}
+
+impl<T: ToVariant> ToVariant for VecDeque<T> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        let array = VariantArray::new();
+        for value in self {
+            array.push(value.to_variant());
+        }
+        array.owned_to_variant()
+    }
+}
+
+impl<T: FromVariant> FromVariant for VecDeque<T> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Vec::<T>::from_variant(variant).map(VecDeque::from)
+    }
+}
+
+impl<T: ToVariant, const N: usize> ToVariant for [T; N] {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        (&self[..]).to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a `VariantArray` of exactly `N` elements.
+impl<T: FromVariant, const N: usize> FromVariant for [T; N] {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let vec = Vec::<T>::from_variant(variant)?;
+        let len = vec.len();
+        vec.try_into()
+            .map_err(|_| FromVariantError::InvalidLength { len, expected: N })
+    }
+}
+
+macro_rules! impl_variant_for_smart_pointer {
+    ($($ptr:ident)*) => {
+        $(
+            impl<T: ToVariant + ?Sized> ToVariant for $ptr<T> {
+                #[inline]
+                fn to_variant(&self) -> Variant {
+                    T::to_variant(self)
+                }
+            }
+            impl<T: ToVariantEq + ?Sized> ToVariantEq for $ptr<T> {}
+
+            impl<T: FromVariant> FromVariant for $ptr<T> {
+                #[inline]
+                fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+                    T::from_variant(variant).map($ptr::new)
+                }
+            }
+        )*
+    };
+}
+
+impl_variant_for_smart_pointer!(Box Rc Arc);
+
+impl<'a, B> ToVariant for Cow<'a, B>
+where
+    B: ToVariant + ToOwned + ?Sized,
+{
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        B::to_variant(self)
+    }
+}
+impl<'a, B> ToVariantEq for Cow<'a, B> where B: ToVariantEq + ToOwned + ?Sized {}
+
+impl<'a, B> FromVariant for Cow<'a, B>
+where
+    B: ToOwned + ?Sized,
+    B::Owned: FromVariant,
+{
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        B::Owned::from_variant(variant).map(Cow::Owned)
+    }
+}
+
+/// Converts the character to a `GodotString` of length 1.
+impl ToVariant for char {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.encode_utf8(&mut [0; 4]).to_variant()
+    }
+}
+impl ToVariantEq for char {}
+
+/// Expects a `Variant` populated with a `GodotString` of exactly one character.
+impl FromVariant for char {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let string = String::from_variant(variant)?;
+        let mut chars = string.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(FromVariantError::InvalidLength {
+                len: string.chars().count(),
+                expected: 1,
+            }),
+        }
+    }
+}
+
+macro_rules! impl_variant_for_non_zero {
+    ($($ty:ident : $int_ty:ty)*) => {
+        $(
+            impl ToVariant for $ty {
+                #[inline]
+                fn to_variant(&self) -> Variant {
+                    self.get().to_variant()
+                }
+            }
+            impl ToVariantEq for $ty {}
+
+            impl FromVariant for $ty {
+                #[inline]
+                fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+                    let i = <$int_ty>::from_variant(variant)?;
+                    $ty::new(i).ok_or_else(|| {
+                        FromVariantError::custom("expected a non-zero integer, got 0")
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_variant_for_non_zero!(
+    NonZeroI8: i8
+    NonZeroI16: i16
+    NonZeroI32: i32
+    NonZeroI64: i64
+    NonZeroIsize: isize
+    NonZeroU8: u8
+    NonZeroU16: u16
+    NonZeroU32: u32
+    NonZeroU64: u64
+    NonZeroUsize: usize
+);
+
+/// Converts the duration to a `f64` number of seconds, the unit used for time in Godot.
+impl ToVariant for Duration {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.as_secs_f64().to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a non-negative `f64` number of seconds.
+impl FromVariant for Duration {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let secs = f64::from_variant(variant)?;
+        // Duration::from_secs_f64 panics on these
+        if !(0.0..u64::MAX as f64).contains(&secs) {
+            return Err(FromVariantError::custom(format!(
+                "expected a non-negative number of seconds, got {}",
+                secs
+            )));
+        }
+        Ok(Duration::from_secs_f64(secs))
+    }
+}
+
+/// Conversions of numeric sequences from and to the matching `PoolArray` type, instead of the
+/// `VariantArray` used by the `ToVariant` and `FromVariant` implementations of `Vec<T>` and
+/// `[T; N]`.
+///
+/// The default implementations convert every element to a `Variant` on its own, which gets
+/// expensive for large amounts of data like mesh or terrain data. The functions in this module
+/// copy all elements at once through [`PoolArray::write`] and [`PoolArray::read`] instead. Since
+/// the pool arrays are allocated by Godot, the elements are still copied once.
+///
+/// Supported are sequences of `u8`, `i32`, `f32`, `Vector2`, `Vector3` and `Color`, which map
+/// to `ByteArray`, `Int32Array`, `Float32Array`, `Vector2Array`, `Vector3Array` and
+/// `ColorArray` respectively.
+///
+/// Intended for use with the `#[variant(with = "gdnative::core_types::as_pool_array")]`
+/// attribute of the derive macros:
+///
+/// ```
+/// use gdnative::prelude::*;
+///
+/// #[derive(ToVariant, FromVariant)]
+/// struct Terrain {
+///     // Represented as `Float32Array` instead of `VariantArray`
+///     #[variant(with = "gdnative::core_types::as_pool_array")]
+///     heights: Vec<f32>,
+///     #[variant(with = "gdnative::core_types::as_pool_array")]
+///     vertices: Vec<Vector3>,
+/// }
+/// ```
+pub mod as_pool_array {
+    use super::*;
+
+    /// Sequences that can be converted from and to a `PoolArray` of their elements.
+    pub trait PoolSequence: Sized {
+        /// The element type, which determines the `PoolArray` type.
+        type Element: PoolElement + Copy;
+
+        /// Returns the elements of the sequence.
+        fn as_elements(&self) -> &[Self::Element];
+
+        /// Creates a sequence by copying `elements`.
+        fn from_elements(elements: &[Self::Element]) -> Result<Self, FromVariantError>;
+    }
+
+    impl<T: PoolElement + Copy> PoolSequence for Vec<T> {
+        type Element = T;
+
+        #[inline]
+        fn as_elements(&self) -> &[T] {
+            self
+        }
+
+        #[inline]
+        fn from_elements(elements: &[T]) -> Result<Self, FromVariantError> {
+            Ok(elements.to_vec())
+        }
+    }
+
+    impl<T: PoolElement + Copy, const N: usize> PoolSequence for [T; N] {
+        type Element = T;
+
+        #[inline]
+        fn as_elements(&self) -> &[T] {
+            self
+        }
+
+        #[inline]
+        fn from_elements(elements: &[T]) -> Result<Self, FromVariantError> {
+            elements
+                .try_into()
+                .map_err(|_| FromVariantError::InvalidLength {
+                    len: elements.len(),
+                    expected: N,
+                })
+        }
+    }
+
+    /// Copies the elements of `seq` to a new `PoolArray`, wrapped in a `Variant`.
+    #[inline]
+    pub fn to_variant<S: PoolSequence>(seq: &S) -> Variant {
+        PoolArray::from_slice(seq.as_elements()).owned_to_variant()
+    }
+
+    /// Expects a `Variant` populated with the `PoolArray` type of the elements of `S`, and
+    /// copies its elements into a new `S`.
+    #[inline]
+    pub fn from_variant<S: PoolSequence>(variant: &Variant) -> Result<S, FromVariantError> {
+        let array = PoolArray::<S::Element>::from_variant(variant)?;
+        let read = array.read();
+        S::from_elements(&read)
+    }
+}
+
+/// Conversions of byte sequences from and to `ByteArray`, instead of the `VariantArray` used by
+/// the `ToVariant` and `FromVariant` implementations of `Vec<u8>` and `[u8; N]`.
+///
+/// Unlike [`as_pool_array`], which also supports other element types, this accepts any type
+/// that can be viewed as or created from bytes, such as `Box<[u8]>`.
+///
+/// Intended for use with the `#[variant(with = "gdnative::core_types::as_byte_array")]`
+/// attribute of the derive macros.
+pub mod as_byte_array {
+    use super::*;
+
+    /// Converts `bytes` to a `ByteArray`, wrapped in a `Variant`.
+    #[inline]
+    pub fn to_variant<T: AsRef<[u8]> + ?Sized>(bytes: &T) -> Variant {
+        ByteArray::from_slice(bytes.as_ref()).owned_to_variant()
+    }
+
+    /// Expects a `Variant` populated with a `ByteArray` and converts it into `T`, which is
+    /// usually `Vec<u8>` or `[u8; N]`.
+    #[inline]
+    pub fn from_variant<T: TryFrom<Vec<u8>>>(variant: &Variant) -> Result<T, FromVariantError> {
+        let bytes = ByteArray::from_variant(variant)?.to_vec();
+        let len = bytes.len();
+        // The expected length cannot be known for any `T`, so only the actual one is reported
+        T::try_from(bytes).map_err(|_| {
+            FromVariantError::Custom(format!(
+                "cannot convert a ByteArray of length {} to {}",
+                len,
+                std::any::type_name::<T>(),
+            ))
+        })
+    }
+}
+
 macro_rules! tuple_length {
     () => { 0usize };
     ($_x:ident, $($xs:ident,)*) => {
@@ -1820,6 +2514,64 @@ godot_test!(
         assert_eq!(Ok(Err(54)), Result::<i64, i64>::from_variant(&dict.into_shared().to_variant()));
     }
 
+    test_from_variant_error_path {
+        use FromVariantError as E;
+
+        let het_array = VariantArray::new();
+        het_array.push(&Variant::new(42));
+        het_array.push(&Variant::nil());
+
+        let dict = Dictionary::new();
+        dict.insert("inventory", het_array.into_shared());
+
+        let error = E::InvalidField {
+            field_name: "inventory",
+            error: Box::new(
+                Vec::<i64>::from_variant(&dict.get_or_nil("inventory"))
+                    .expect_err("should fail on nil"),
+            ),
+        };
+
+        assert_eq!(
+            vec![ErrorPathSegment::Field("inventory"), ErrorPathSegment::Index(1)],
+            error.path(),
+        );
+        assert_eq!(
+            &E::InvalidVariantType {
+                expected: VariantType::I64,
+                variant_type: VariantType::Nil,
+            },
+            error.innermost(),
+        );
+        assert_eq!(
+            "inventory[1]: invalid variant type: expected I64, got Nil",
+            error.to_compact_string(),
+        );
+
+        let error = E::InvalidEnumVariant {
+            variant: "Player",
+            error: Box::new(E::Multiple(vec![
+                error,
+                E::InvalidField {
+                    field_name: "name",
+                    error: Box::new(E::InvalidNil),
+                },
+            ])),
+        };
+
+        let all_errors = error.all_errors();
+        assert_eq!(2, all_errors.len());
+        assert_eq!(
+            vec![ErrorPathSegment::Variant("Player"), ErrorPathSegment::Field("name")],
+            all_errors[1].0,
+        );
+        assert_eq!(&E::InvalidNil, all_errors[1].1);
+        assert_eq!(
+            "Player.inventory[1]: invalid variant type: expected I64, got Nil; Player.name: expected non-nullable type, got null",
+            error.to_compact_string(),
+        );
+    }
+
     test_to_variant_iter {
         let slice: &[i64] = &[0, 1, 2, 3, 4];
         let variant = slice.to_variant();
@@ -1958,6 +2710,87 @@ godot_test!(
         );
     }
 
+    test_variant_std_types {
+        use std::collections::{BTreeMap, BTreeSet, VecDeque};
+        use std::num::NonZeroU32;
+        use std::time::Duration;
+
+        let map = BTreeMap::from([("b".to_string(), 2_i64), ("a".to_string(), 1)]);
+        let dict = map.to_variant().try_to::<Dictionary>().expect("should be dictionary");
+        assert_eq!(Ok("a".to_string()), dict.keys().get(0).try_to::<String>());
+        assert_eq!(Ok(map), dict.owned_to_variant().try_to::<BTreeMap<String, i64>>());
+
+        let mut index_map = IndexMap::<String, i64>::new();
+        index_map.insert("z".to_string(), 1);
+        index_map.insert("a".to_string(), 2);
+        let check_index_map = index_map
+            .to_variant()
+            .try_to::<IndexMap<String, i64>>()
+            .expect("should be index map");
+        assert_eq!(vec!["z", "a"], check_index_map.keys().collect::<Vec<_>>());
+
+        let set = BTreeSet::from([3_i64, 1, 2]);
+        assert_eq!(Ok(vec![1, 2, 3]), set.to_variant().try_to::<Vec<i64>>());
+        assert_eq!(Ok(set), vec![3_i64, 1, 2].to_variant().try_to::<BTreeSet<i64>>());
+
+        let deque = VecDeque::from([1_i64, 2]);
+        assert_eq!(Ok(deque.clone()), deque.to_variant().try_to::<VecDeque<i64>>());
+
+        let array = [1_i64, 2, 3];
+        assert_eq!(Ok(array), array.to_variant().try_to::<[i64; 3]>());
+        assert_eq!(
+            Err(FromVariantError::InvalidLength { len: 3, expected: 2 }),
+            array.to_variant().try_to::<[i64; 2]>(),
+        );
+
+        assert_eq!(Ok(Box::new(42_i64)), 42.to_variant().try_to::<Box<i64>>());
+        assert_eq!(Ok(Rc::new(42_i64)), 42.to_variant().try_to::<Rc<i64>>());
+        assert_eq!(Ok(Arc::new(42_i64)), 42.to_variant().try_to::<Arc<i64>>());
+        let cow = Cow::<str>::Borrowed("Foo");
+        assert_eq!(Ok(cow.clone()), cow.to_variant().try_to::<Cow<str>>());
+
+        assert_eq!(Ok('é'), 'é'.to_variant().try_to::<char>());
+        assert_eq!(
+            Err(FromVariantError::InvalidLength { len: 3, expected: 1 }),
+            "Foo".to_variant().try_to::<char>(),
+        );
+
+        let non_zero = NonZeroU32::new(7).unwrap();
+        assert_eq!(Ok(non_zero), non_zero.to_variant().try_to::<NonZeroU32>());
+        assert!(0.to_variant().try_to::<NonZeroU32>().is_err());
+
+        let duration = Duration::from_millis(1500);
+        assert_eq!(Ok(1.5), duration.to_variant().try_to::<f64>());
+        assert_eq!(Ok(duration), duration.to_variant().try_to::<Duration>());
+        assert!((-1.0).to_variant().try_to::<Duration>().is_err());
+
+        let bytes = vec![1_u8, 2, 3];
+        let variant = as_pool_array::to_variant(&bytes);
+        assert_eq!(VariantType::ByteArray, variant.get_type());
+        assert_eq!(Ok(bytes), as_pool_array::from_variant::<Vec<u8>>(&variant));
+        assert_eq!(Ok([1, 2, 3]), as_pool_array::from_variant::<[u8; 3]>(&variant));
+        assert_eq!(
+            Err(FromVariantError::InvalidLength { len: 3, expected: 4 }),
+            as_pool_array::from_variant::<[u8; 4]>(&variant),
+        );
+        let slice_variant = as_byte_array::to_variant(&[1_u8, 2, 3][..]);
+        assert_eq!(Ok(vec![1, 2, 3]), as_pool_array::from_variant::<Vec<u8>>(&slice_variant));
+        assert_eq!(
+            Ok(Box::from(&[1_u8, 2, 3][..])),
+            as_byte_array::from_variant::<Box<[u8]>>(&variant),
+        );
+        assert!(matches!(
+            as_byte_array::from_variant::<[u8; 4]>(&variant),
+            Err(FromVariantError::Custom(message)) if message.contains("length 3"),
+        ));
+
+        let vertices = vec![Vector3::new(1.0, 2.0, 3.0), Vector3::new(4.0, 5.0, 6.0)];
+        let variant = as_pool_array::to_variant(&vertices);
+        assert_eq!(VariantType::Vector3Array, variant.get_type());
+        assert_eq!(Ok(vertices), as_pool_array::from_variant::<Vec<Vector3>>(&variant));
+        assert!(as_pool_array::from_variant::<Vec<f32>>(&variant).is_err());
+    }
+
     test_variant_tuple {
         let variant = (42i64, 54i64).to_variant();
         let arr = variant.try_to::<VariantArray>().expect("should be array");
@@ -104,6 +104,50 @@ pub trait NativeClass: Sized + 'static {
     }
 }
 
+/// Trait for [`NativeClass`]es that can be created from Rust with arguments of type `Args`,
+/// using [`Instance::new_with`].
+///
+/// Unlike a value passed to [`Instance::emplace`], the constructor receives the newly created
+/// owner along with the arguments, so it can configure both at creation. A class can implement
+/// this trait for several argument types. Multiple arguments can be passed as a tuple.
+///
+/// This is independent of the zero-argument constructor used by `Instance::new` and by
+/// `ScriptName.new()` in GDScript, so classes with only argument constructors can be marked
+/// with `#[no_constructor]`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gdnative::prelude::*;
+/// use gdnative::export::NativeClassCtor;
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node2D)]
+/// #[no_constructor]
+/// struct Mob {
+///     speed: f32,
+/// }
+///
+/// #[methods]
+/// impl Mob {}
+///
+/// impl NativeClassCtor<(Vector2, f32)> for Mob {
+///     fn ctor(owner: TRef<Node2D>, (position, speed): (Vector2, f32)) -> Self {
+///         owner.set_position(position);
+///         Mob { speed }
+///     }
+/// }
+///
+/// fn spawn(parent: &Node) {
+///     let mob = Instance::<Mob, Unique>::new_with((Vector2::new(10.0, 20.0), 150.0));
+///     parent.add_child(mob.into_base(), false);
+/// }
+/// ```
+pub trait NativeClassCtor<Args>: NativeClass {
+    /// Creates a value of `Self` for the new instance owned by `owner`, from `args`.
+    fn ctor(owner: TRef<'_, Self::Base, Shared>, args: Args) -> Self;
+}
+
 /// A NativeScript "class" that is statically named. [`NativeClass`] types that implement this
 /// trait can be registered using  [`InitHandle::add_class`].
 pub trait StaticallyNamed: NativeClass {
@@ -124,6 +168,57 @@ pub trait NativeClassMethods: NativeClass {
     fn register(builder: &ClassBuilder<Self>);
 }
 
+/// Trait for types that register additional members of the `NativeClass` `C`, merged with the
+/// ones of the class when it is registered.
+///
+/// Mixins are usually generated by `#[methods(mixin = "Name")]`, which exports the methods of
+/// an `impl` block through the type `Name` instead of [`NativeClassMethods`], so a class can
+/// export methods from several `impl` blocks, including `impl SomeTrait for MyClass` blocks.
+/// They are added to the class with [`ClassBuilder::mixin`] or the `#[mixins(...)]` attribute
+/// of the `NativeClass` derive macro.
+///
+/// Mixins can also be implemented manually. A type implementing `Mixin<C>` for every `C` that
+/// implements some trait can register the same members on many classes.
+///
+/// # Examples
+///
+/// ```
+/// use gdnative::prelude::*;
+///
+/// trait Damageable {
+///     fn take_damage(&mut self, amount: i64);
+/// }
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// #[mixins(CrateDamageable)]
+/// #[no_constructor]
+/// struct Crate {
+///     health: i64,
+/// }
+///
+/// #[methods]
+/// impl Crate {
+///     #[method]
+///     fn health(&self) -> i64 {
+///         self.health
+///     }
+/// }
+///
+/// // Exports `take_damage` through the mixin type `CrateDamageable`
+/// #[methods(mixin = "CrateDamageable")]
+/// impl Damageable for Crate {
+///     #[method]
+///     fn take_damage(&mut self, amount: i64) {
+///         self.health -= amount;
+///     }
+/// }
+/// ```
+pub trait Mixin<C: NativeClass> {
+    /// Registers the members of this mixin on the class being registered by `builder`.
+    fn register(builder: &ClassBuilder<C>);
+}
+
 /// Trait for types that can be used as the `owner` arguments of exported methods. This trait
 /// is sealed and has no public interface.
 ///
@@ -1,10 +1,11 @@
+use std::any::TypeId;
 use std::ffi::CString;
 use std::marker::PhantomData;
 use std::ptr;
 
 use crate::core_types::{GodotString, VariantType};
 use crate::export::*;
-use crate::object::NewRef;
+use crate::object::{NewRef, SubClass};
 use crate::private::get_api;
 
 // TODO unify string parameters across all buiders
@@ -20,6 +21,10 @@ use crate::private::get_api;
 pub struct ClassBuilder<C> {
     pub(super) init_handle: *mut libc::c_void,
     pub(super) class_name: CString,
+    /// Type of the class being registered, which is not `C` when registering a super class.
+    pub(super) class_type: TypeId,
+    /// Upcasts from the class being registered to `C`.
+    pub(super) upcasts: Vec<super::super_class::Upcast>,
     _marker: PhantomData<C>,
 }
 
@@ -28,10 +33,49 @@ impl<C: NativeClass> ClassBuilder<C> {
         Self {
             init_handle,
             class_name,
+            class_type: TypeId::of::<C>(),
+            upcasts: Vec::new(),
             _marker: PhantomData,
         }
     }
 
+    /// Returns a builder that registers the members of the super class `S` on the same class.
+    pub(super) fn for_super_class<S: NativeClass>(
+        &self,
+        upcasts: Vec<super::super_class::Upcast>,
+    ) -> ClassBuilder<S> {
+        ClassBuilder {
+            init_handle: self.init_handle,
+            class_name: self.class_name.clone(),
+            class_type: self.class_type,
+            upcasts,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the documentation of the class being registered, which is shown in the editor and
+    /// included in [`class_reference_xml`]. The first paragraph is used as the brief
+    /// description.
+    ///
+    /// Has no effect when registering the members of a super class with
+    /// [`super_class`](Self::super_class), so sub classes do not inherit its description.
+    #[inline]
+    pub fn class_documentation(&self, documentation: &str) {
+        if !self.upcasts.is_empty() {
+            return;
+        }
+
+        super::class_docs::record_class(self.class_type, documentation);
+
+        unsafe {
+            (get_api().godot_nativescript_set_class_documentation)(
+                self.init_handle,
+                self.class_name.as_ptr(),
+                GodotString::from(documentation).to_sys(),
+            );
+        }
+    }
+
     /// Returns a `MethodBuilder` which can be used to add a method to the class being
     /// registered.
     ///
@@ -118,6 +162,61 @@ impl<C: NativeClass> ClassBuilder<C> {
         PropertyBuilder::new(self, name)
     }
 
+    /// Starts a group of properties in the inspector. Properties registered after this call
+    /// are shown under a collapsible section named `name`.
+    ///
+    /// If `prefix` is not empty, only the following properties whose names start with `prefix`
+    /// are part of the group, and the prefix is removed from their displayed names. Otherwise,
+    /// the group lasts until the next group or category. An empty `name` ends the current group.
+    ///
+    /// Godot stores inspector headers as properties of the class named after the header, which
+    /// are also visible to `get` and `set`. Registering a header with the same name twice, e.g.
+    /// ending groups with an empty `name` more than once, replaces the earlier header instead
+    /// of adding a new one. Prefer prefixes to end groups where this matters.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use gdnative::prelude::*;
+    ///
+    /// #[derive(NativeClass)]
+    /// #[inherit(Node)]
+    /// #[register_with(Self::my_register)]
+    /// #[no_constructor]
+    /// struct MyType {
+    ///     speed: f32,
+    /// }
+    ///
+    /// impl MyType {
+    ///     fn my_register(builder: &ClassBuilder<MyType>) {
+    ///         builder.property_group("Movement", "movement_");
+    ///
+    ///         // Shown as "Speed" in the "Movement" group
+    ///         builder
+    ///             .property::<f32>("movement_speed")
+    ///             .with_getter(|this: &MyType, _| this.speed)
+    ///             .with_setter(|this: &mut MyType, _, v| this.speed = v)
+    ///             .done();
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    pub fn property_group(&self, name: &str, prefix: &str) {
+        super::property::register_header(self, name, prefix, PropertyUsage::GROUP);
+    }
+
+    /// Starts a category of properties in the inspector. Properties registered after this call
+    /// are shown under a header named `name`, until the next category.
+    ///
+    /// As with [`property_group`](Self::property_group), the name of each category may only be
+    /// used once per class.
+    #[inline]
+    pub fn property_category(&self, name: &str) {
+        super::property::register_header(self, name, "", PropertyUsage::CATEGORY);
+    }
+
     /// Returns a `SignalBuilder` which can be used to add a signal to the class being
     /// registered.
     ///
@@ -154,6 +253,13 @@ impl<C: NativeClass> ClassBuilder<C> {
     ///             .with_param("fuel_spent", VariantType::I64)
     ///             .with_param_default("fuel_type", Variant::new("Kerosene"))
     ///             .done();
+    ///
+    ///         // Add fourth signal with parameter types inferred from Rust types
+    ///         builder
+    ///             .signal("picked_up")
+    ///             .with_typed_param::<Ref<Texture>>("icon")
+    ///             .with_typed_param::<f32>("weight")
+    ///             .done();
     ///     }
     /// }
     /// ```
@@ -162,8 +268,138 @@ impl<C: NativeClass> ClassBuilder<C> {
         SignalBuilder::new(self, GodotString::from(name))
     }
 
+    /// Registers an integer constant named `name` on the class being registered.
+    ///
+    /// NativeScript has no API for class-level constants, so the constant is registered as a
+    /// read-only property that always returns `value`. It can be read from GDScript through any
+    /// instance of the class, e.g. `node.STATE_IDLE`, but not through the script resource
+    /// itself. Reading it never accesses the user data, so it is safe to use during other calls
+    /// into the instance. For the same reason, there is no way to register static methods.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use gdnative::prelude::*;
+    ///
+    /// #[derive(NativeClass)]
+    /// #[inherit(Node)]
+    /// #[register_with(Self::my_register)]
+    /// #[no_constructor]
+    /// struct MyType {}
+    ///
+    /// // Note: no #[methods] required
+    /// impl MyType {
+    ///     fn my_register(builder: &ClassBuilder<MyType>) {
+    ///         builder.constant("MAX_HEALTH", 100);
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    pub fn constant(&self, name: &str, value: i64) {
+        PropertyBuilder::<C, i64>::new(self, name)
+            .with_const_getter(value)
+            .with_default(value)
+            .with_usage(PropertyUsage::empty())
+            .done();
+    }
+
+    /// Registers every variant of the enum `E` as an integer constant, using
+    /// [`constant`](Self::constant).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use gdnative::prelude::*;
+    ///
+    /// #[export_enum]
+    /// enum State {
+    ///     Idle,
+    ///     Running,
+    /// }
+    ///
+    /// #[derive(NativeClass)]
+    /// #[inherit(Node)]
+    /// #[register_with(Self::my_register)]
+    /// #[no_constructor]
+    /// struct MyType {}
+    ///
+    /// impl MyType {
+    ///     fn my_register(builder: &ClassBuilder<MyType>) {
+    ///         // Registers STATE_IDLE = 0 and STATE_RUNNING = 1
+    ///         builder.enum_constants::<State>();
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    pub fn enum_constants<E: EnumConstants>(&self) {
+        for (name, value) in E::constants() {
+            self.constant(name, value);
+        }
+    }
+
+    /// Registers the members of the [`Mixin`] `M` on the class being registered.
+    ///
+    /// This is done automatically for the mixins listed in the `#[mixins(...)]` attribute of
+    /// the `NativeClass` derive macro. See [`Mixin`] for an example.
+    #[inline]
+    pub fn mixin<M: Mixin<C>>(&self) {
+        M::register(self);
+    }
+
+    /// Registers the properties of the [`DynamicProperties`] implementation of the class being
+    /// registered, by adding the `_get_property_list`, `_get` and `_set` methods. These methods
+    /// must not also be exported through `#[methods]`.
+    ///
+    /// See [`DynamicProperties`] for an example.
+    #[inline]
+    pub fn dynamic_properties(&self)
+    where
+        C: DynamicProperties,
+        C::UserData: user_data::Map + user_data::MapMut,
+    {
+        super::dynamic_property::register(self);
+    }
+
+    /// Registers the hooks of the [`LifecycleHooks`] implementation of the class being
+    /// registered, by adding the `_notification` method. This method must not also be exported
+    /// through `#[methods]`.
+    ///
+    /// This is done automatically by the `#[lifecycle_hooks]` attribute of the `NativeClass`
+    /// derive macro. See [`LifecycleHooks`] for an example.
+    #[inline]
+    pub fn lifecycle_hooks(&self)
+    where
+        C: LifecycleHooks,
+        C::UserData: user_data::MapMut,
+    {
+        super::lifecycle::register(self);
+    }
+
+    /// Registers the properties, methods and signals of the [`NativeSubClass::Super`] class of
+    /// the class being registered. They are called with the super class data returned by
+    /// [`NativeSubClass::super_data`].
+    ///
+    /// This should be called before registering the members of the class itself, so they can
+    /// override the ones of the super class with the same names. The `#[super_class]` field
+    /// attribute of the `NativeClass` derive macro does this automatically. See
+    /// [`NativeSubClass`] for an example.
     #[inline]
-    pub(crate) fn add_signal(&self, signal: Signal) {
+    pub fn super_class(&self)
+    where
+        C: NativeSubClass,
+        C::UserData: user_data::Map,
+        C::Super: NativeClassMethods,
+        C::Base: SubClass<<C::Super as NativeClass>::Base>,
+    {
+        super::super_class::register(self);
+    }
+
+    #[inline]
+    pub(crate) fn add_signal(&self, signal: ScriptSignal) {
         unsafe {
             let args_and_hints = signal
                 .args
@@ -197,7 +433,31 @@ impl<C: NativeClass> ClassBuilder<C> {
                     default_args: ptr::null_mut(),
                 },
             );
+
+            if let Some(documentation) = &signal.documentation {
+                let signal_name = CString::new(signal.name.to_string()).unwrap();
+                (get_api().godot_nativescript_set_signal_documentation)(
+                    self.init_handle,
+                    self.class_name.as_ptr(),
+                    signal_name.as_ptr(),
+                    documentation.to_sys(),
+                );
+            }
         }
+
+        super::class_docs::record_signal(
+            self.class_type,
+            &signal.name.to_string(),
+            signal
+                .args
+                .iter()
+                .map(|arg| (arg.name.to_string(), &arg.export_info)),
+            signal
+                .documentation
+                .as_ref()
+                .map(|doc| doc.to_string())
+                .as_deref(),
+        );
     }
 
     /// Returns the declared parameter type, or the default value's type, or Nil (in that order)
@@ -225,12 +485,16 @@ impl<C: NativeClass> ClassBuilder<C> {
 
         let attr = sys::godot_method_attributes { rpc_type: rpc };
 
-        let method_desc = sys::godot_instance_method {
+        let mut method_desc = sys::godot_instance_method {
             method: method.method_ptr,
             method_data: method.method_data,
             free_func: method.free_func,
         };
 
+        if !self.upcasts.is_empty() {
+            method_desc = super::super_class::upcast_method(&self.upcasts, method_desc);
+        }
+
         unsafe {
             (get_api().godot_nativescript_register_method)(
                 self.init_handle,
@@ -240,5 +504,49 @@ impl<C: NativeClass> ClassBuilder<C> {
                 method_desc,
             );
         }
+
+        if !method.args.is_empty() {
+            let sys_args = method
+                .args
+                .iter()
+                .map(|arg| sys::godot_method_arg {
+                    name: arg.name.to_sys(),
+                    type_: arg.export_info.variant_type as sys::godot_variant_type,
+                    hint: arg.export_info.hint_kind,
+                    hint_string: arg.export_info.hint_string.to_sys(),
+                })
+                .collect::<Vec<_>>();
+
+            unsafe {
+                (get_api().godot_nativescript_set_method_argument_information)(
+                    self.init_handle,
+                    self.class_name.as_ptr() as *const _,
+                    method_name.as_ptr() as *const _,
+                    sys_args.len() as libc::c_int,
+                    sys_args.as_ptr(),
+                );
+            }
+        }
+
+        if let Some(documentation) = method.documentation {
+            unsafe {
+                (get_api().godot_nativescript_set_method_documentation)(
+                    self.init_handle,
+                    self.class_name.as_ptr() as *const _,
+                    method_name.as_ptr() as *const _,
+                    GodotString::from(documentation).to_sys(),
+                );
+            }
+        }
+
+        super::class_docs::record_method(
+            self.class_type,
+            method.name,
+            method
+                .args
+                .iter()
+                .map(|arg| (arg.name.to_string(), &arg.export_info)),
+            method.documentation,
+        );
     }
 }
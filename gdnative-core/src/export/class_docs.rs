@@ -0,0 +1,260 @@
+//! Documentation of registered classes, and its export in Godot's class reference format.
+
+use std::any::TypeId;
+use std::fmt::Write;
+
+use crate::core_types::{Variant, VariantType};
+use crate::export::{class_registry, ExportInfo, NativeClass};
+
+/// Documentation and members of a registered class, collected during registration.
+#[derive(Debug, Default)]
+pub(crate) struct ClassDocs {
+    pub inherits: &'static str,
+    pub description: Option<String>,
+    pub methods: Vec<MemberDocs>,
+    pub members: Vec<PropertyDocs>,
+    pub signals: Vec<MemberDocs>,
+}
+
+/// A method or a signal.
+#[derive(Debug)]
+pub(crate) struct MemberDocs {
+    pub name: String,
+    pub args: Vec<(String, &'static str)>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct PropertyDocs {
+    pub name: String,
+    pub ty: &'static str,
+    pub default: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Replaces the entry named `name` in `entries`, or adds it if there is none. Members registered
+/// later override earlier ones in Godot, e.g. the ones of a super class.
+fn upsert<T>(entries: &mut Vec<T>, entry: T, name: impl Fn(&T) -> &str) {
+    match entries.iter_mut().find(|e| name(e) == name(&entry)) {
+        Some(existing) => *existing = entry,
+        None => entries.push(entry),
+    }
+}
+
+pub(crate) fn record_class(class: TypeId, description: &str) {
+    class_registry::with_class_docs_mut(class, |docs| {
+        docs.description = Some(description.to_string());
+    });
+}
+
+pub(crate) fn record_method<'a>(
+    class: TypeId,
+    name: &str,
+    args: impl Iterator<Item = (String, &'a ExportInfo)>,
+    description: Option<&str>,
+) {
+    let method = MemberDocs {
+        name: name.to_string(),
+        args: args.map(|(name, info)| (name, type_name(info))).collect(),
+        description: description.map(str::to_string),
+    };
+    class_registry::with_class_docs_mut(class, |docs| {
+        upsert(&mut docs.methods, method, |m| &m.name)
+    });
+}
+
+pub(crate) fn record_property(
+    class: TypeId,
+    name: &str,
+    export_info: &ExportInfo,
+    default: &Variant,
+    description: Option<&str>,
+) {
+    let default = match default.get_type() {
+        VariantType::Nil => None,
+        VariantType::GodotString => Some(format!("\"{}\"", default)),
+        _ => Some(default.to_string()),
+    };
+    let property = PropertyDocs {
+        name: name.to_string(),
+        ty: type_name(export_info),
+        default,
+        description: description.map(str::to_string),
+    };
+    class_registry::with_class_docs_mut(class, |docs| {
+        upsert(&mut docs.members, property, |p| &p.name)
+    });
+}
+
+pub(crate) fn record_signal<'a>(
+    class: TypeId,
+    name: &str,
+    args: impl Iterator<Item = (String, &'a ExportInfo)>,
+    description: Option<&str>,
+) {
+    let signal = MemberDocs {
+        name: name.to_string(),
+        args: args.map(|(name, info)| (name, type_name(info))).collect(),
+        description: description.map(str::to_string),
+    };
+    class_registry::with_class_docs_mut(class, |docs| {
+        upsert(&mut docs.signals, signal, |s| &s.name)
+    });
+}
+
+/// Returns the name of the type described by `export_info`, as used in the class reference.
+fn type_name(export_info: &ExportInfo) -> &'static str {
+    match export_info.variant_type {
+        VariantType::Nil => "Variant",
+        VariantType::Bool => "bool",
+        VariantType::I64 => "int",
+        VariantType::F64 => "float",
+        VariantType::GodotString => "String",
+        VariantType::Vector2 => "Vector2",
+        VariantType::Rect2 => "Rect2",
+        VariantType::Vector3 => "Vector3",
+        VariantType::Transform2D => "Transform2D",
+        VariantType::Plane => "Plane",
+        VariantType::Quat => "Quat",
+        VariantType::Aabb => "AABB",
+        VariantType::Basis => "Basis",
+        VariantType::Transform => "Transform",
+        VariantType::Color => "Color",
+        VariantType::NodePath => "NodePath",
+        VariantType::Rid => "RID",
+        VariantType::Object => "Object",
+        VariantType::Dictionary => "Dictionary",
+        VariantType::VariantArray => "Array",
+        VariantType::ByteArray => "PoolByteArray",
+        VariantType::Int32Array => "PoolIntArray",
+        VariantType::Float32Array => "PoolRealArray",
+        VariantType::StringArray => "PoolStringArray",
+        VariantType::Vector2Array => "PoolVector2Array",
+        VariantType::Vector3Array => "PoolVector3Array",
+        VariantType::ColorArray => "PoolColorArray",
+    }
+}
+
+/// Returns the class reference of the registered class `C` as XML, in the format of the
+/// `doc_classes` directories of Godot and its modules, or `None` if `C` is not registered.
+///
+/// The reference lists all properties, methods and signals registered for the class, with the
+/// documentation set through the builders, or with doc comments when using the derive macros.
+/// Return types of methods are not known, so they are omitted.
+///
+/// This can be used to generate reference pages for the classes of a library, e.g. from a tool
+/// script or an integration test that writes the XML files to disk.
+#[inline]
+pub fn class_reference_xml<C: NativeClass>() -> Option<String> {
+    class_registry::with_class_info::<C, _, _>(|info| write_class_xml(&info.name, &info.docs))
+}
+
+fn write_class_xml(name: &str, docs: &ClassDocs) -> String {
+    let mut xml = String::new();
+    let description = docs.description.as_deref().unwrap_or_default();
+    let brief_description = description.split("\n\n").next().unwrap_or_default();
+
+    // Writing to a String cannot fail
+    let _ = writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8" ?>"#);
+    let _ = writeln!(
+        xml,
+        r#"<class name="{}" inherits="{}">"#,
+        escape(name),
+        escape(docs.inherits),
+    );
+    write_description(&mut xml, 1, "brief_description", brief_description);
+    write_description(&mut xml, 1, "description", description);
+    let _ = writeln!(xml, "\t<tutorials>\n\t</tutorials>");
+
+    let _ = writeln!(xml, "\t<methods>");
+    for method in &docs.methods {
+        let _ = writeln!(xml, r#"		<method name="{}">"#, escape(&method.name));
+        write_arguments(&mut xml, &method.args);
+        write_description(
+            &mut xml,
+            3,
+            "description",
+            description_of(&method.description),
+        );
+        let _ = writeln!(xml, "\t\t</method>");
+    }
+    let _ = writeln!(xml, "\t</methods>");
+
+    let _ = writeln!(xml, "\t<members>");
+    for member in &docs.members {
+        let _ = write!(
+            xml,
+            r#"		<member name="{}" type="{}""#,
+            escape(&member.name),
+            member.ty,
+        );
+        if let Some(default) = &member.default {
+            let _ = write!(xml, r#" default="{}""#, escape(default));
+        }
+        let _ = writeln!(
+            xml,
+            ">\n\t\t\t{}\n\t\t</member>",
+            escape(description_of(&member.description)),
+        );
+    }
+    let _ = writeln!(xml, "\t</members>");
+
+    let _ = writeln!(xml, "\t<signals>");
+    for signal in &docs.signals {
+        let _ = writeln!(xml, r#"		<signal name="{}">"#, escape(&signal.name));
+        write_arguments(&mut xml, &signal.args);
+        write_description(
+            &mut xml,
+            3,
+            "description",
+            description_of(&signal.description),
+        );
+        let _ = writeln!(xml, "\t\t</signal>");
+    }
+    let _ = writeln!(xml, "\t</signals>");
+
+    let _ = writeln!(xml, "\t<constants>\n\t</constants>");
+    let _ = writeln!(xml, "</class>");
+    xml
+}
+
+fn description_of(description: &Option<String>) -> &str {
+    description.as_deref().unwrap_or_default()
+}
+
+fn write_arguments(xml: &mut String, args: &[(String, &'static str)]) {
+    for (index, (name, ty)) in args.iter().enumerate() {
+        let _ = writeln!(
+            xml,
+            r#"			<argument index="{}" name="{}" type="{}" />"#,
+            index,
+            escape(name),
+            ty,
+        );
+    }
+}
+
+fn write_description(xml: &mut String, indent: usize, tag: &str, text: &str) {
+    let tabs = "\t".repeat(indent);
+    let _ = writeln!(
+        xml,
+        "{tabs}<{tag}>\n{tabs}\t{text}\n{tabs}</{tag}>",
+        tabs = tabs,
+        tag = tag,
+        text = escape(text),
+    );
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
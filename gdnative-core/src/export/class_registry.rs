@@ -5,13 +5,26 @@ use std::collections::HashMap;
 use once_cell::sync::Lazy;
 use parking_lot::RwLock;
 
+use crate::export::class_docs::ClassDocs;
+use crate::export::super_class::Upcast;
 use crate::export::NativeClass;
 
 static CLASS_REGISTRY: Lazy<RwLock<HashMap<TypeId, ClassInfo>>> =
     Lazy::new(|| RwLock::new(HashMap::new()));
 
+/// Names of classes that are about to be registered, so that classes registered before them
+/// can already refer to them.
+static DECLARED_NAMES: Lazy<RwLock<HashMap<TypeId, Cow<'static, str>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
 pub(crate) struct ClassInfo {
     pub name: Cow<'static, str>,
+    pub type_name: &'static str,
+    /// Rust super classes registered with `ClassBuilder::super_class`, with the upcasts to
+    /// them.
+    pub super_classes: Vec<(TypeId, Vec<Upcast>)>,
+    /// Documentation and members of the class, for the class reference.
+    pub docs: ClassDocs,
 }
 
 /// Access the [`ClassInfo`] of the class `C`.
@@ -30,6 +43,13 @@ pub(crate) fn class_name<C: NativeClass>() -> Option<Cow<'static, str>> {
     with_class_info::<C, _, _>(|i| i.name.clone())
 }
 
+/// Returns the NativeScript name of the class `C` if it is registered or declared with
+/// `declare_class_name`.
+#[inline]
+pub(crate) fn registered_or_declared_name<C: NativeClass>() -> Option<Cow<'static, str>> {
+    class_name::<C>().or_else(|| DECLARED_NAMES.read().get(&TypeId::of::<C>()).cloned())
+}
+
 /// Returns the NativeScript name of the class `C` if it is registered, or a best-effort description
 /// of the type otherwise.
 ///
@@ -45,11 +65,74 @@ pub(crate) fn class_name_or_default<C: NativeClass>() -> Cow<'static, str> {
 #[inline]
 pub(crate) fn register_class_as<C: NativeClass>(name: Cow<'static, str>) -> Option<ClassInfo> {
     let type_id = TypeId::of::<C>();
-    CLASS_REGISTRY.write().insert(type_id, ClassInfo { name })
+    let type_name = std::any::type_name::<C>();
+    CLASS_REGISTRY.write().insert(
+        type_id,
+        ClassInfo {
+            name,
+            type_name,
+            super_classes: Vec::new(),
+            docs: ClassDocs {
+                inherits: <C::Base as crate::object::GodotObject>::class_name(),
+                ..Default::default()
+            },
+        },
+    )
+}
+
+/// Declares that the class `C` is about to be registered as `name`.
+#[cfg(feature = "inventory")]
+#[inline]
+pub(crate) fn declare_class_name<C: NativeClass>(name: Cow<'static, str>) {
+    DECLARED_NAMES.write().insert(TypeId::of::<C>(), name);
+}
+
+/// Records that the class with type ID `class` extends `super_class`, which can be reached
+/// through `upcasts`.
+#[inline]
+pub(crate) fn register_super_class(class: TypeId, super_class: TypeId, upcasts: Vec<Upcast>) {
+    if let Some(info) = CLASS_REGISTRY.write().get_mut(&class) {
+        info.super_classes.push((super_class, upcasts));
+    }
+}
+
+/// Access the [`ClassDocs`] of the class with type ID `class` mutably, if it is registered.
+#[inline]
+pub(crate) fn with_class_docs_mut<F>(class: TypeId, f: F)
+where
+    F: FnOnce(&mut ClassDocs),
+{
+    if let Some(info) = CLASS_REGISTRY.write().get_mut(&class) {
+        f(&mut info.docs);
+    }
+}
+
+/// Returns the upcasts from the class with type ID `class` to its super class `super_class`,
+/// if `class` extends it.
+#[inline]
+pub(crate) fn find_upcasts(class: TypeId, super_class: TypeId) -> Option<Vec<Upcast>> {
+    CLASS_REGISTRY.read().get(&class).and_then(|info| {
+        info.super_classes
+            .iter()
+            .find(|(type_id, _)| *type_id == super_class)
+            .map(|(_, upcasts)| upcasts.clone())
+    })
+}
+
+/// Returns the Rust type name of a class other than `C` that is registered as `name`, if any.
+#[inline]
+pub(crate) fn find_name_conflict<C: NativeClass>(name: &str) -> Option<&'static str> {
+    let type_id = TypeId::of::<C>();
+    CLASS_REGISTRY
+        .read()
+        .iter()
+        .find(|(other_id, info)| **other_id != type_id && info.name == name)
+        .map(|(_, info)| info.type_name)
 }
 
 /// Clears the registry
 #[inline]
 pub(crate) fn cleanup() {
     CLASS_REGISTRY.write().clear();
+    DECLARED_NAMES.write().clear();
 }
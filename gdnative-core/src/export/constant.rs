@@ -0,0 +1,33 @@
+//! Integer constants exported from Rust enums.
+
+/// Trait for fieldless enums whose variants can be registered as integer constants on a
+/// `NativeClass`, using [`ClassBuilder::enum_constants`][crate::export::ClassBuilder::enum_constants].
+///
+/// This is usually derived through the `#[export_enum]` attribute, which also implements
+/// `ToVariant` and `FromVariant` so the enum can be used in exported method signatures.
+///
+/// # Examples
+///
+/// Manual implementation:
+///
+/// ```
+/// use gdnative::export::EnumConstants;
+///
+/// enum State {
+///     Idle,
+///     Running,
+/// }
+///
+/// impl EnumConstants for State {
+///     fn constants() -> Vec<(&'static str, i64)> {
+///         vec![
+///             ("STATE_IDLE", State::Idle as i64),
+///             ("STATE_RUNNING", State::Running as i64),
+///         ]
+///     }
+/// }
+/// ```
+pub trait EnumConstants {
+    /// Returns the name and value of each constant, in declaration order.
+    fn constants() -> Vec<(&'static str, i64)>;
+}
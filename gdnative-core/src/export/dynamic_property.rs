@@ -0,0 +1,251 @@
+//! Properties that depend on instance state.
+
+use std::marker::PhantomData;
+
+use crate::core_types::{Dictionary, GodotString, ToVariant, Variant, VariantArray, VariantType};
+use crate::export::user_data::{Map, MapMut};
+use crate::export::{
+    ClassBuilder, Export, ExportInfo, Method, NativeClass, PropertyUsage, Varargs,
+};
+use crate::log::Site;
+use crate::object::{TInstance, TRef};
+
+/// Description of a property returned from [`DynamicProperties::property_list`].
+#[derive(Debug)]
+pub struct DynamicProperty {
+    name: GodotString,
+    export_info: ExportInfo,
+    usage: PropertyUsage,
+}
+
+impl DynamicProperty {
+    /// Creates a description of the property `name`, with the given type and hint, and the
+    /// default usage.
+    #[inline]
+    pub fn new(name: impl Into<GodotString>, export_info: ExportInfo) -> Self {
+        DynamicProperty {
+            name: name.into(),
+            export_info,
+            usage: PropertyUsage::DEFAULT,
+        }
+    }
+
+    /// Creates a description of the property `name`, inferring its type from the `Export`
+    /// implementation of `T`.
+    #[inline]
+    pub fn typed<T: Export>(name: impl Into<GodotString>, hint: Option<T::Hint>) -> Self {
+        Self::new(name, T::export_info(hint))
+    }
+
+    /// Sets a property usage.
+    #[inline]
+    pub fn with_usage(mut self, usage: PropertyUsage) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    /// Converts this description into the dictionary format expected by `_get_property_list`.
+    fn to_dictionary(&self) -> Dictionary {
+        let dict = Dictionary::new();
+        dict.insert("name", &self.name);
+        dict.insert("type", self.export_info.variant_type as i64);
+        dict.insert("hint", self.export_info.hint_kind as i64);
+        dict.insert("hint_string", &self.export_info.hint_string);
+        dict.insert("usage", self.usage.bits() as i64);
+        dict.into_shared()
+    }
+}
+
+/// Trait for `NativeClass`es whose properties depend on instance state, like the parameters
+/// of Godot's own `AnimationTree`.
+///
+/// The properties are registered with
+/// [`ClassBuilder::dynamic_properties`](crate::export::ClassBuilder::dynamic_properties), which wires this
+/// trait to the `_get_property_list`, `_get` and `_set` virtual methods. They are listed in
+/// the inspector and `Object::get_property_list`, and can be accessed like any other property
+/// with `Object::get` and `Object::set`. Properties registered with `ClassBuilder::property`
+/// take precedence over dynamic properties of the same name.
+///
+/// The list is queried again whenever the inspector is refreshed. Call
+/// `Object::property_list_changed_notify` on the owner after the list changes.
+///
+/// Dynamic properties cannot be accessed while the instance is already borrowed, for example
+/// with `Object::get` on the owner from inside a `&mut self` method. Such accesses silently fall
+/// back to the properties of the base class.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use gdnative::prelude::*;
+/// use gdnative::export::{DynamicProperties, DynamicProperty};
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// #[register_with(Self::my_register)]
+/// #[no_constructor]
+/// struct Parameters {
+///     values: HashMap<String, f64>,
+/// }
+///
+/// impl Parameters {
+///     fn my_register(builder: &ClassBuilder<Parameters>) {
+///         builder.dynamic_properties();
+///     }
+/// }
+///
+/// impl DynamicProperties for Parameters {
+///     fn property_list(&self, _owner: TRef<Node>) -> Vec<DynamicProperty> {
+///         self.values
+///             .keys()
+///             .map(|name| DynamicProperty::typed::<f64>(format!("parameters/{}", name), None))
+///             .collect()
+///     }
+///
+///     fn get_property(&self, _owner: TRef<Node>, name: &str) -> Option<Variant> {
+///         let name = name.strip_prefix("parameters/")?;
+///         self.values.get(name).map(|value| value.to_variant())
+///     }
+///
+///     fn set_property(&mut self, _owner: TRef<Node>, name: &str, value: Variant) -> bool {
+///         let name = match name.strip_prefix("parameters/") {
+///             Some(name) => name,
+///             None => return false,
+///         };
+///         match (self.values.get_mut(name), value.to::<f64>()) {
+///             (Some(slot), Some(value)) => {
+///                 *slot = value;
+///                 true
+///             }
+///             _ => false,
+///         }
+///     }
+/// }
+/// ```
+pub trait DynamicProperties: NativeClass {
+    /// Returns the properties currently available on this instance.
+    fn property_list(&self, owner: TRef<'_, Self::Base>) -> Vec<DynamicProperty>;
+
+    /// Returns the value of the property `name`, or `None` if there is no such property.
+    fn get_property(&self, owner: TRef<'_, Self::Base>, name: &str) -> Option<Variant>;
+
+    /// Sets the property `name` to `value`. Returns `false` if there is no such property, or
+    /// if the value cannot be assigned to it.
+    fn set_property(&mut self, owner: TRef<'_, Self::Base>, name: &str, value: Variant) -> bool;
+}
+
+/// Registers the methods that expose the `DynamicProperties` implementation of `C` to Godot.
+pub(super) fn register<C>(class_builder: &ClassBuilder<C>)
+where
+    C: DynamicProperties,
+    C::UserData: Map + MapMut,
+{
+    class_builder
+        .method("_get_property_list", GetPropertyList(PhantomData))
+        .done();
+    class_builder
+        .method("_get", GetProperty(PhantomData))
+        .with_typed_arg::<GodotString>("property")
+        .done();
+    class_builder
+        .method("_set", SetProperty(PhantomData))
+        .with_typed_arg::<GodotString>("property")
+        .with_arg("value", ExportInfo::new(VariantType::Nil))
+        .done();
+}
+
+fn log_map_error<E: std::fmt::Debug>(err: E) -> Variant {
+    godot_error!(
+        "gdnative-core: dynamic property access failed with error: {:?}",
+        err
+    );
+    godot_error!(
+        "gdnative-core: check module level documentation on gdnative::user_data for more information"
+    );
+    Variant::nil()
+}
+
+struct GetPropertyList<C>(PhantomData<fn() -> C>);
+
+impl<C> Method<C> for GetPropertyList<C>
+where
+    C: DynamicProperties,
+    C::UserData: Map,
+{
+    fn call(&self, this: TInstance<'_, C>, args: Varargs<'_>) -> Variant {
+        if let Err(err) = args.done() {
+            err.with_site(Self::site().unwrap_or_default()).log_error();
+            return Variant::nil();
+        }
+
+        this.map(|script, owner| {
+            let list = VariantArray::new();
+            for property in script.property_list(owner) {
+                list.push(property.to_dictionary());
+            }
+            list.into_shared().to_variant()
+        })
+        .unwrap_or_else(log_map_error)
+    }
+
+    fn site() -> Option<Site<'static>> {
+        Some(godot_site!(DynamicProperties::property_list))
+    }
+}
+
+struct GetProperty<C>(PhantomData<fn() -> C>);
+
+impl<C> Method<C> for GetProperty<C>
+where
+    C: DynamicProperties,
+    C::UserData: Map,
+{
+    fn call(&self, this: TInstance<'_, C>, mut args: Varargs<'_>) -> Variant {
+        let name = match args.read::<String>().get() {
+            Ok(name) => name,
+            Err(err) => {
+                err.with_site(Self::site().unwrap_or_default()).log_error();
+                return Variant::nil();
+            }
+        };
+
+        // Godot calls `_get` for every property of the owner, including the ones of the base
+        // class, so a failed borrow is not an error: nil falls back to the normal lookup
+        this.map(|script, owner| script.get_property(owner, &name).unwrap_or_default())
+            .unwrap_or_default()
+    }
+
+    fn site() -> Option<Site<'static>> {
+        Some(godot_site!(DynamicProperties::get_property))
+    }
+}
+
+struct SetProperty<C>(PhantomData<fn() -> C>);
+
+impl<C> Method<C> for SetProperty<C>
+where
+    C: DynamicProperties,
+    C::UserData: MapMut,
+{
+    fn call(&self, this: TInstance<'_, C>, mut args: Varargs<'_>) -> Variant {
+        let name = args.read::<String>().get();
+        let value = args.read::<Variant>().get();
+
+        let (name, value) = match (name, value) {
+            (Ok(name), Ok(value)) => (name, value),
+            (Err(err), _) | (_, Err(err)) => {
+                err.with_site(Self::site().unwrap_or_default()).log_error();
+                return Variant::nil();
+            }
+        };
+
+        // See `GetProperty::call`
+        this.map_mut(|script, owner| script.set_property(owner, &name, value).to_variant())
+            .unwrap_or_default()
+    }
+
+    fn site() -> Option<Site<'static>> {
+        Some(godot_site!(DynamicProperties::set_property))
+    }
+}
@@ -4,23 +4,29 @@ use std::any::Any;
 use std::cell::RefCell;
 
 use crate::export::class_registry;
+use crate::object::ownership::Shared;
+use crate::object::TRef;
 
 use super::NativeClass;
 
+/// Function creating the script value from its owner, stored for emplacement.
+pub type Constructor<T> = Box<dyn FnOnce(TRef<'_, <T as NativeClass>::Base, Shared>) -> T>;
+
 thread_local! {
     static CELL: RefCell<Option<Box<dyn Any>>> = RefCell::default();
 }
 
-/// Place a script to be taken by the emplacement constructor. Must be called
-/// directly before `NativeScript::_new` for intended behavior.
+/// Place a constructor to be called by the emplacement constructor instead of
+/// `NativeClass::init`. Must be called directly before `NativeScript::_new` for
+/// intended behavior.
 ///
 /// # Panics
 ///
 /// If there is already a value placed for this thread, or if the thread is
 /// exiting. This is always a bug in the bindings.
-pub fn place<T: NativeClass>(script: T) {
+pub fn place<T: NativeClass>(constructor: Constructor<T>) {
     CELL.with(|f| {
-        if f.replace(Some(Box::new(script))).is_some() {
+        if f.replace(Some(Box::new(constructor))).is_some() {
             panic!(
                 "there is already a value in the emplacement cell (this is a bug in the bindings)"
             );
@@ -28,17 +34,17 @@ pub fn place<T: NativeClass>(script: T) {
     });
 }
 
-/// Take the script stored for emplacement and return it. Returns `None` if
+/// Take the constructor stored for emplacement and return it. Returns `None` if
 /// there is no value in store.
 ///
 /// # Panics
 ///
 /// If there is a value in store but it is of the incorrect type. This is always
 /// a bug in the bindings.
-pub fn take<T: NativeClass>() -> Option<T> {
+pub fn take<T: NativeClass>() -> Option<Constructor<T>> {
     CELL.with(|f| f.borrow_mut().take())
-        .map(|script| match script.downcast() {
-            Ok(script) => *script,
+        .map(|constructor| match constructor.downcast() {
+            Ok(constructor) => *constructor,
             Err(any) => panic!(
                 "expecting {} in the emplacement cell, got {:?} (this is a bug in the bindings)",
                 class_registry::class_name_or_default::<T>(),
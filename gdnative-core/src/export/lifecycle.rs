@@ -0,0 +1,176 @@
+//! Hooks into the lifecycle of script instances.
+
+use std::marker::PhantomData;
+
+use crate::core_types::Variant;
+use crate::export::user_data::MapMut;
+use crate::export::{ClassBuilder, Method, NativeClass, Varargs};
+use crate::log::Site;
+use crate::object::{GodotObject, TInstance, TRef};
+
+/// `Object::NOTIFICATION_PREDELETE`
+const NOTIFICATION_PREDELETE: i64 = 1;
+
+/// `Node::NOTIFICATION_READY`
+const NOTIFICATION_READY: i64 = 13;
+
+/// Trait for `NativeClass`es that react to notifications received by their owner, such as the
+/// owner becoming ready or being about to be freed.
+///
+/// The hooks are registered with
+/// [`ClassBuilder::lifecycle_hooks`](crate::export::ClassBuilder::lifecycle_hooks), or with the
+/// `#[lifecycle_hooks]` attribute of the `NativeClass` derive macro, which wires this trait to the
+/// `_notification` virtual method. All hooks are optional and do nothing by default.
+///
+/// Unlike `Drop`, [`on_predelete`](Self::on_predelete) is called while the owner is still
+/// alive, so it can be used to disconnect signals or free resources held by the owner. It is
+/// not called for instances that are dropped without their owner being freed, such as after
+/// the script is detached.
+///
+/// The values of notifications are only unique within a class and its subclasses, so
+/// [`on_notification`](Self::on_notification) must check them against the constants of the
+/// base class, or of one of its base classes. Typed constants are generated for each API class
+/// in its module, like `gdnative::api::node::Notification::READY`.
+///
+/// Notifications received while the instance is already borrowed, for example one caused by a
+/// call on the owner from inside a `&mut self` method, cannot be delivered and are reported as
+/// errors.
+///
+/// # Examples
+///
+/// ```
+/// use gdnative::prelude::*;
+/// use gdnative::api::node;
+/// use gdnative::export::{LifecycleHooks, Notification};
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// #[lifecycle_hooks]
+/// #[no_constructor]
+/// struct Tracker {
+///     parented: u32,
+/// }
+///
+/// impl LifecycleHooks for Tracker {
+///     fn on_ready(&mut self, owner: TRef<Node>) {
+///         godot_print!("{} is ready", owner.name());
+///     }
+///
+///     fn on_predelete(&mut self, owner: TRef<Node>) {
+///         godot_print!("{} is about to be freed", owner.name());
+///     }
+///
+///     fn on_notification(&mut self, _owner: TRef<Node>, what: Notification) {
+///         if what == node::Notification::PARENTED {
+///             self.parented += 1;
+///         }
+///     }
+/// }
+/// ```
+pub trait LifecycleHooks: NativeClass {
+    /// Called when the owner receives `Node::NOTIFICATION_READY`, after it and its children
+    /// have entered the scene tree. Only called if the owner is a `Node`, since other classes
+    /// may use the same value for unrelated notifications.
+    #[inline]
+    fn on_ready(&mut self, _owner: TRef<'_, Self::Base>) {}
+
+    /// Called when the owner receives `Object::NOTIFICATION_PREDELETE`, right before it is
+    /// freed. The owner is still valid during the call.
+    #[inline]
+    fn on_predelete(&mut self, _owner: TRef<'_, Self::Base>) {}
+
+    /// Called for every notification received by the owner, including the ones handled by
+    /// [`on_ready`](Self::on_ready) and [`on_predelete`](Self::on_predelete), after those.
+    /// `what` can be compared against the typed constants of the API classes, such as
+    /// `gdnative::api::node::Notification::PAUSED`.
+    #[inline]
+    fn on_notification(&mut self, _owner: TRef<'_, Self::Base>, _what: Notification) {}
+}
+
+/// A notification received by the owner of a script instance, as passed to
+/// [`LifecycleHooks::on_notification`].
+///
+/// Can be compared with the typed constants generated in the module of each API class, like
+/// `gdnative::api::node::Notification::READY`, or converted from and to the integer values of
+/// the `NOTIFICATION_*` constants of the API classes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Notification(pub i64);
+
+impl From<i64> for Notification {
+    #[inline]
+    fn from(v: i64) -> Self {
+        Self(v)
+    }
+}
+
+impl From<Notification> for i64 {
+    #[inline]
+    fn from(v: Notification) -> Self {
+        v.0
+    }
+}
+
+/// Registers the `_notification` method that exposes the `LifecycleHooks` implementation of `C`
+/// to Godot.
+pub(super) fn register<C>(class_builder: &ClassBuilder<C>)
+where
+    C: LifecycleHooks,
+    C::UserData: MapMut,
+{
+    class_builder
+        .method("_notification", NotificationHandler(PhantomData))
+        .with_typed_arg::<i64>("what")
+        .done();
+}
+
+struct NotificationHandler<C>(PhantomData<fn() -> C>);
+
+impl<C> Method<C> for NotificationHandler<C>
+where
+    C: LifecycleHooks,
+    C::UserData: MapMut,
+{
+    fn call(&self, this: TInstance<'_, C>, mut args: Varargs<'_>) -> Variant {
+        let what = match args.read::<i64>().get() {
+            Ok(what) => what,
+            Err(err) => {
+                err.with_site(Self::site().unwrap_or_default()).log_error();
+                return Variant::nil();
+            }
+        };
+
+        if let Err(err) = args.done() {
+            err.with_site(Self::site().unwrap_or_default()).log_error();
+            return Variant::nil();
+        }
+
+        let result = this.map_mut(|script, owner| {
+            match what {
+                NOTIFICATION_READY if owner.as_raw().is_class_by_name("Node") => {
+                    script.on_ready(owner)
+                }
+                NOTIFICATION_PREDELETE => script.on_predelete(owner),
+                _ => {}
+            }
+            script.on_notification(owner, Notification(what));
+        });
+
+        if let Err(err) = result {
+            godot_error!(
+                "gdnative-core: notification {} could not be delivered to {}: {:?}",
+                what,
+                crate::export::class_registry::class_name_or_default::<C>(),
+                err,
+            );
+            godot_error!(
+                "gdnative-core: check module level documentation on gdnative::user_data for more information"
+            );
+        }
+
+        Variant::nil()
+    }
+
+    fn site() -> Option<Site<'static>> {
+        Some(godot_site!(LifecycleHooks::on_notification))
+    }
+}
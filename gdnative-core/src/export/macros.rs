@@ -32,6 +32,41 @@ macro_rules! deprecated_export_syntax {
     () => {};
 }
 
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "inventory")]
+macro_rules! godot_auto_register_class {
+    ($type_name:ty, $add_fn:ident) => {
+        const _: () = {
+            fn __register(handle: $crate::init::InitHandle) {
+                handle.$add_fn::<$type_name>();
+            }
+
+            fn __declare() {
+                $crate::private::declare_auto_class::<$type_name>();
+            }
+
+            $crate::private::inventory::submit! {
+                $crate::private::AutoInitPlugin {
+                    f: __register,
+                    declare: __declare,
+                }
+            }
+        };
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "inventory"))]
+macro_rules! godot_auto_register_class {
+    ($type_name:ty, $add_fn:ident) => {
+        ::std::compile_error!(
+            "`#[auto_register]` requires the `inventory` feature of the `gdnative` crate"
+        );
+    };
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! godot_wrap_method_void {
@@ -51,7 +86,7 @@ macro_rules! godot_wrap_method_inner {
             $self:ident
             $(, #[base] $base:ident : $base_ty:ty)?
             $(, $pname:ident : $pty:ty)*
-            $(, #[opt] $opt_pname:ident : $opt_pty:ty)*
+            $(, #[opt $($opt_args:tt)*] $opt_pname:ident : $opt_pty:ty)*
         ) -> $retty:ty
     ) => {
         {
@@ -66,7 +101,7 @@ macro_rules! godot_wrap_method_inner {
             #[allow(clippy::used_underscore_binding)]
             struct Args {
                 $($pname: $pty,)*
-                $(#[opt] $opt_pname: $opt_pty,)*
+                $(#[opt $($opt_args)*] $opt_pname: $opt_pty,)*
             }
 
             #[allow(unused_variables, unused_assignments, unused_mut)]
@@ -131,7 +166,7 @@ macro_rules! godot_wrap_method {
             &mut $self:ident
             $(, #[base] $base:ident : $base_ty:ty)?
             $(, $pname:ident : $pty:ty)*
-            $(, #[opt] $opt_pname:ident : $opt_pty:ty)*
+            $(, #[opt $($opt_args:tt)*] $opt_pname:ident : $opt_pty:ty)*
             $(,)?
         ) $(-> $retty:ty)?
     ) => {
@@ -143,7 +178,7 @@ macro_rules! godot_wrap_method {
                 $self
                 $(, #[base] $base : $base_ty)?
                 $(, $pname : $pty)*
-                $(, #[opt] $opt_pname : $opt_pty)*
+                $(, #[opt $($opt_args)*] $opt_pname : $opt_pty)*
             ) -> godot_wrap_method_return_type!($($retty)?)
         )
     };
@@ -155,7 +190,7 @@ macro_rules! godot_wrap_method {
             & $self:ident
             $(, #[base] $base:ident : $base_ty:ty)?
             $(, $pname:ident : $pty:ty)*
-            $(, #[opt] $opt_pname:ident : $opt_pty:ty)*
+            $(, #[opt $($opt_args:tt)*] $opt_pname:ident : $opt_pty:ty)*
             $(,)?
         ) $(-> $retty:ty)?
     ) => {
@@ -167,7 +202,7 @@ macro_rules! godot_wrap_method {
                 $self
                 $(, #[base] $base : $base_ty)?
                 $(, $pname : $pty)*
-                $(, #[opt] $opt_pname : $opt_pty)*
+                $(, #[opt $($opt_args)*] $opt_pname : $opt_pty)*
             ) -> godot_wrap_method_return_type!($($retty)?)
         )
     };
@@ -179,7 +214,7 @@ macro_rules! godot_wrap_method {
             mut $self:ident
             $(, #[base] $base:ident : $base_ty:ty)?
             $(, $pname:ident : $pty:ty)*
-            $(, #[opt] $opt_pname:ident : $opt_pty:ty)*
+            $(, #[opt $($opt_args:tt)*] $opt_pname:ident : $opt_pty:ty)*
             $(,)?
         ) $(-> $retty:ty)?
     ) => {
@@ -191,7 +226,7 @@ macro_rules! godot_wrap_method {
                 $self
                 $(, #[base] $base : $base_ty)?
                 $(, $pname : $pty)*
-                $(, #[opt] $opt_pname : $opt_pty)*
+                $(, #[opt $($opt_args)*] $opt_pname : $opt_pty)*
             ) -> godot_wrap_method_return_type!($($retty)?)
         )
     };
@@ -203,7 +238,7 @@ macro_rules! godot_wrap_method {
             $self:ident
             $(, #[base] $base:ident : $base_ty:ty)?
             $(, $pname:ident : $pty:ty)*
-            $(, #[opt] $opt_pname:ident : $opt_pty:ty)*
+            $(, #[opt $($opt_args:tt)*] $opt_pname:ident : $opt_pty:ty)*
             $(,)?
         ) $(-> $retty:ty)?
     ) => {
@@ -215,7 +250,7 @@ macro_rules! godot_wrap_method {
                 $self
                 $(, #[base] $base : $base_ty)?
                 $(, $pname : $pty)*
-                $(, #[opt] $opt_pname : $opt_pty)*
+                $(, #[opt $($opt_args)*] $opt_pname : $opt_pty)*
             ) -> godot_wrap_method_return_type!($($retty)?)
         )
     };
@@ -4,9 +4,9 @@ use std::borrow::Cow;
 use std::marker::PhantomData;
 use std::{fmt, ops};
 
-use crate::core_types::{FromVariant, FromVariantError, Variant};
+use crate::core_types::{FromVariant, FromVariantError, GodotString, Variant, VariantType};
 use crate::export::class::NativeClass;
-use crate::export::{class_registry, ClassBuilder};
+use crate::export::{class_registry, ClassBuilder, Export, ExportInfo};
 use crate::log::Site;
 use crate::object::ownership::Shared;
 use crate::object::{Ref, TInstance, TRef};
@@ -19,6 +19,8 @@ pub struct MethodBuilder<'a, C, F> {
     method: F,
 
     rpc_mode: RpcMode,
+    args: Vec<ScriptMethodArg>,
+    documentation: Option<&'a str>,
 }
 
 impl<'a, C, F> MethodBuilder<'a, C, F>
@@ -32,6 +34,8 @@ where
             name,
             method,
             rpc_mode: RpcMode::Disabled,
+            args: Vec::new(),
+            documentation: None,
         }
     }
 
@@ -42,6 +46,36 @@ where
         self
     }
 
+    /// Adds information about the next argument of this method. The information is reported to
+    /// Godot for use by the editor and by `Object::get_method_list`, and is not checked against
+    /// the arguments the method actually accepts.
+    ///
+    /// Arguments should be added in the order they appear in the method signature, excluding
+    /// `self` and the base object.
+    #[inline]
+    pub fn with_arg(mut self, name: &str, export_info: ExportInfo) -> Self {
+        self.args.push(ScriptMethodArg {
+            name: GodotString::from(name),
+            export_info,
+        });
+        self
+    }
+
+    /// Adds information about the next argument of this method, inferring its type from the
+    /// `Export` implementation of `T`. See [`with_arg`](Self::with_arg).
+    #[inline]
+    pub fn with_typed_arg<T: Export>(self, name: &str) -> Self {
+        self.with_arg(name, T::export_info(None))
+    }
+
+    /// Sets the documentation of this method, which is shown in the editor and included in
+    /// [`class_reference_xml`](crate::export::class_reference_xml).
+    #[inline]
+    pub fn with_documentation(mut self, documentation: &'a str) -> Self {
+        self.documentation = Some(documentation);
+        self
+    }
+
     /// Register the method.
     #[inline]
     pub fn done(self) {
@@ -55,6 +89,8 @@ where
             },
             method_data: method_data as *mut libc::c_void,
             free_func: Some(free_func::<F>),
+            args: self.args,
+            documentation: self.documentation,
         };
 
         self.class_builder.add_method(script_method);
@@ -82,6 +118,8 @@ where
             // a valid pointer for it.
             method_data: 1 as *mut libc::c_void,
             free_func: None,
+            args: self.args,
+            documentation: self.documentation,
         };
 
         self.class_builder.add_method(script_method);
@@ -124,6 +162,69 @@ pub(crate) struct ScriptMethod<'l> {
 
     pub method_data: *mut libc::c_void,
     pub free_func: Option<unsafe extern "C" fn(*mut libc::c_void) -> ()>,
+
+    pub args: Vec<ScriptMethodArg>,
+    pub documentation: Option<&'l str>,
+}
+
+pub(crate) struct ScriptMethodArg {
+    pub name: GodotString,
+    pub export_info: ExportInfo,
+}
+
+/// Helper used by the `#[methods]` macro to report argument types. Arguments of types that
+/// implement `Export` use its `ExportInfo`, while other arguments are reported as untyped.
+///
+/// This relies on method resolution preferring `ArgInfoFromExport`, which applies to the
+/// probe itself, over `ArgInfoFallback`, which only applies to a reference to it:
+///
+/// ```ignore
+/// (&ArgInfoProbe::<T>::new()).arg_export_info()
+/// ```
+#[doc(hidden)]
+pub struct ArgInfoProbe<T> {
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ArgInfoProbe<T> {
+    #[doc(hidden)]
+    #[inline]
+    pub fn new() -> Self {
+        ArgInfoProbe {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for ArgInfoProbe<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[doc(hidden)]
+pub trait ArgInfoFromExport {
+    fn arg_export_info(&self) -> ExportInfo;
+}
+
+impl<T: Export> ArgInfoFromExport for ArgInfoProbe<T> {
+    #[inline]
+    fn arg_export_info(&self) -> ExportInfo {
+        T::export_info(None)
+    }
+}
+
+#[doc(hidden)]
+pub trait ArgInfoFallback {
+    fn arg_export_info(&self) -> ExportInfo;
+}
+
+impl<T> ArgInfoFallback for &ArgInfoProbe<T> {
+    #[inline]
+    fn arg_export_info(&self) -> ExportInfo {
+        ExportInfo::new(VariantType::Nil)
+    }
 }
 
 /// Safe low-level trait for stateful, variadic methods that can be called on a native script type.
@@ -264,7 +365,7 @@ impl<'a> Varargs<'a> {
             name: None,
             ty: None,
             site: None,
-            _marker: PhantomData,
+            default: None,
         }
     }
 
@@ -616,7 +717,7 @@ pub struct ArgBuilder<'r, 'a, T> {
     name: Option<Cow<'a, str>>,
     ty: Option<Cow<'a, str>>,
     site: Option<Site<'a>>,
-    _marker: PhantomData<T>,
+    default: Option<T>,
 }
 
 impl<'r, 'a, T> ArgBuilder<'r, 'a, T> {
@@ -646,14 +747,24 @@ impl<'r, 'a, T> ArgBuilder<'r, 'a, T> {
         self.site = Some(site);
         self
     }
+
+    /// Provides a default value for this argument, used if the argument is missing. If an old
+    /// default value is already set, it is silently replaced.
+    #[inline]
+    pub fn with_default(mut self, default: T) -> Self {
+        self.default = Some(default);
+        self
+    }
 }
 
 impl<'r, 'a, T: FromVariant> ArgBuilder<'r, 'a, T> {
-    /// Get the converted argument value.
+    /// Get the converted argument value, or the default value if one is set and the argument
+    /// is missing.
     ///
     /// # Errors
     ///
-    /// If the argument is missing, or cannot be converted to the desired type.
+    /// If the argument is missing without a default value, or cannot be converted to the
+    /// desired type.
     #[inline]
     pub fn get(mut self) -> Result<T, ArgumentError<'a>> {
         self.get_optional_internal().and_then(|arg| {
@@ -668,7 +779,8 @@ impl<'r, 'a, T: FromVariant> ArgBuilder<'r, 'a, T> {
         })
     }
 
-    /// Get the argument as optional.
+    /// Get the argument as optional. If the argument is missing, the default value is returned
+    /// if one is set.
     ///
     /// # Errors
     ///
@@ -684,7 +796,7 @@ impl<'r, 'a, T: FromVariant> ArgBuilder<'r, 'a, T> {
             args,
             name,
             ty,
-            ..
+            default,
         } = self;
         let actual_index = args.idx + args.offset_index;
 
@@ -702,7 +814,7 @@ impl<'r, 'a, T: FromVariant> ArgBuilder<'r, 'a, T> {
                 },
             })
         } else {
-            Ok(None)
+            Ok(default.take())
         }
     }
 }
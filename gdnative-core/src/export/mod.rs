@@ -15,22 +15,38 @@
 
 mod class;
 mod class_builder;
+mod class_docs;
+mod constant;
+mod dynamic_property;
+mod lifecycle;
 mod macros;
 mod method;
+mod nested_property;
 mod property;
 mod signal;
+mod virtual_method;
 
 pub(crate) mod class_registry;
 pub(crate) mod emplace;
+pub(crate) mod super_class;
 pub(crate) mod type_tag;
 
 pub mod user_data;
 
+#[doc(hidden)]
+pub use crate::godot_auto_register_class;
 pub use crate::godot_wrap_method;
 #[allow(deprecated)]
 pub use crate::{deprecated_export_syntax, deprecated_reference_return};
 pub use class::*;
 pub use class_builder::*;
+pub use class_docs::class_reference_xml;
+pub use constant::*;
+pub use dynamic_property::*;
+pub use lifecycle::*;
 pub use method::*;
+pub use nested_property::*;
 pub use property::*;
 pub use signal::*;
+pub use super_class::{NativeSubClass, Super};
+pub use virtual_method::*;
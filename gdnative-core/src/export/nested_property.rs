@@ -0,0 +1,154 @@
+//! Properties that are fields of nested structs.
+
+use std::fmt;
+
+use crate::export::user_data::{Map, MapMut};
+use crate::export::{ClassBuilder, NativeClass};
+
+/// Trait for plain Rust structs whose fields can be exported as properties of the
+/// `NativeClass` containing them, usually implemented with `#[derive(NestedProperties)]`.
+///
+/// Each field is registered as a property named `{prefix}/{field}`, where `prefix` is usually
+/// the name of the field holding the struct. The inspector shows them in a collapsible section
+/// named after the prefix.
+///
+/// # Examples
+///
+/// ```
+/// use gdnative::prelude::*;
+/// use gdnative::export::hint::{FloatHint, RangeHint};
+///
+/// #[derive(NestedProperties, Default)]
+/// struct MovementConfig {
+///     #[property(hint = "FloatHint::Range(RangeHint::new(0.0, 500.0))")]
+///     speed: f64,
+///     #[property]
+///     name: String,
+/// }
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// #[no_constructor]
+/// struct Player {
+///     // Registers the properties `config/speed` and `config/name`
+///     #[property(nested)]
+///     config: MovementConfig,
+/// }
+/// ```
+pub trait NestedProperties: Sized + 'static {
+    /// Registers the properties of `Self` on the class `C`, with names starting with `prefix/`.
+    /// `lens` is used to access the value of `Self` from instances of `C`.
+    fn register_properties<C, L>(builder: &ClassBuilder<C>, prefix: &str, lens: L)
+    where
+        C: NativeClass,
+        C::UserData: Map + MapMut,
+        L: PropertyLens<C, Self>;
+}
+
+/// Access path from a value of type `C` to a value of type `T` nested inside it, used to
+/// register [`NestedProperties`].
+pub trait PropertyLens<C, T>: Copy + 'static {
+    /// Returns a reference to the nested value.
+    fn get<'a>(&self, this: &'a C) -> &'a T;
+
+    /// Returns a mutable reference to the nested value.
+    fn get_mut<'a>(&self, this: &'a mut C) -> &'a mut T;
+
+    /// Returns a lens to a field `U` of the nested value, given functions that access the field.
+    #[inline]
+    fn field<U>(
+        self,
+        get: fn(&T) -> &U,
+        get_mut: fn(&mut T) -> &mut U,
+    ) -> ComposedLens<Self, T, U> {
+        ComposedLens {
+            outer: self,
+            inner: FieldLens::new(get, get_mut),
+        }
+    }
+}
+
+/// Lens to a field of `C`, defined by functions that access the field.
+pub struct FieldLens<C, T> {
+    get: fn(&C) -> &T,
+    get_mut: fn(&mut C) -> &mut T,
+}
+
+impl<C, T> FieldLens<C, T> {
+    /// Creates a lens from functions that access the field, usually non-capturing closures
+    /// like `|this: &Foo| &this.bar`.
+    #[inline]
+    pub fn new(get: fn(&C) -> &T, get_mut: fn(&mut C) -> &mut T) -> Self {
+        FieldLens { get, get_mut }
+    }
+}
+
+impl<C, T> Clone for FieldLens<C, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C, T> Copy for FieldLens<C, T> {}
+
+impl<C, T> fmt::Debug for FieldLens<C, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FieldLens").finish()
+    }
+}
+
+impl<C: 'static, T: 'static> PropertyLens<C, T> for FieldLens<C, T> {
+    #[inline]
+    fn get<'a>(&self, this: &'a C) -> &'a T {
+        (self.get)(this)
+    }
+
+    #[inline]
+    fn get_mut<'a>(&self, this: &'a mut C) -> &'a mut T {
+        (self.get_mut)(this)
+    }
+}
+
+/// Lens to a field `U` of a value `T` accessed through another lens. Returned by
+/// [`PropertyLens::field`].
+pub struct ComposedLens<L, T, U> {
+    outer: L,
+    inner: FieldLens<T, U>,
+}
+
+impl<L: Copy, T, U> Clone for ComposedLens<L, T, U> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L: Copy, T, U> Copy for ComposedLens<L, T, U> {}
+
+impl<L: fmt::Debug, T, U> fmt::Debug for ComposedLens<L, T, U> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComposedLens")
+            .field("outer", &self.outer)
+            .finish()
+    }
+}
+
+impl<C, L, T, U> PropertyLens<C, U> for ComposedLens<L, T, U>
+where
+    L: PropertyLens<C, T>,
+    T: 'static,
+    U: 'static,
+{
+    #[inline]
+    fn get<'a>(&self, this: &'a C) -> &'a U {
+        self.inner.get(self.outer.get(this))
+    }
+
+    #[inline]
+    fn get_mut<'a>(&self, this: &'a mut C) -> &'a mut U {
+        self.inner.get_mut(self.outer.get_mut(this))
+    }
+}
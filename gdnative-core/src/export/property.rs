@@ -1,17 +1,20 @@
 //! Property registration.
+use std::cell::Cell;
 use std::marker::PhantomData;
 
-use accessor::{Getter, RawGetter, RawSetter, Setter};
+use accessor::{ConstGetter, Getter, RawGetter, RawSetter, Setter};
 use invalid_accessor::{InvalidGetter, InvalidSetter};
+use setter_hooks::{SetterHooks, SetterValidator};
 
 use crate::core_types::*;
-use crate::export::{ClassBuilder, NativeClass};
+use crate::export::{class_registry, ClassBuilder, NativeClass};
 use crate::object::ownership::Shared;
 use crate::object::{GodotObject, Instance, Ref};
 use crate::private::get_api;
 
 mod accessor;
 mod invalid_accessor;
+mod setter_hooks;
 
 pub mod hint;
 
@@ -63,6 +66,61 @@ impl ExportInfo {
     }
 }
 
+/// Helper used by the `NativeClass` derive macro to accept both hint functions and hint values
+/// in `#[property(hint = "...")]`. Functions returning a hint are called, while other values are
+/// converted with `Into`.
+///
+/// This relies on method resolution preferring `HintFromFn`, which applies to the argument
+/// itself, over `HintFromValue`, which only applies to a reference to it:
+///
+/// ```ignore
+/// (&HintArg::new(expr)).into_hint()
+/// ```
+#[doc(hidden)]
+pub struct HintArg<T>(Cell<Option<T>>);
+
+impl<T> HintArg<T> {
+    #[doc(hidden)]
+    #[inline]
+    pub fn new(value: T) -> Self {
+        HintArg(Cell::new(Some(value)))
+    }
+
+    fn take(&self) -> T {
+        self.0.take().expect("hint should only be taken once")
+    }
+}
+
+#[doc(hidden)]
+pub trait HintFromFn<H> {
+    fn into_hint(&self) -> H;
+}
+
+impl<F, H> HintFromFn<H> for HintArg<F>
+where
+    F: FnOnce() -> H,
+{
+    #[inline]
+    fn into_hint(&self) -> H {
+        (self.take())()
+    }
+}
+
+#[doc(hidden)]
+pub trait HintFromValue<H> {
+    fn into_hint(&self) -> H;
+}
+
+impl<V, H> HintFromValue<H> for &HintArg<V>
+where
+    V: Into<H>,
+{
+    #[inline]
+    fn into_hint(&self) -> H {
+        self.take().into()
+    }
+}
+
 /// Builder type used to register a property on a `NativeClass`.
 #[derive(Debug)]
 #[must_use = "PropertyBuilder left unbuilt -- did you forget to call done()?"]
@@ -73,6 +131,8 @@ pub struct PropertyBuilder<'a, C, T: Export, S = InvalidSetter<'a>, G = InvalidG
     default: Option<T>,
     hint: Option<T::Hint>,
     usage: PropertyUsage,
+    hooks: SetterHooks,
+    documentation: Option<&'a str>,
     class_builder: &'a ClassBuilder<C>,
 }
 
@@ -91,6 +151,8 @@ where
             default: None,
             hint: None,
             usage: PropertyUsage::DEFAULT,
+            hooks: SetterHooks::default(),
+            documentation: None,
             class_builder,
         }
     }
@@ -106,37 +168,26 @@ where
     /// Register the property built with this builder.
     #[inline]
     pub fn done(self) {
-        let ExportInfo {
-            variant_type,
-            hint_kind,
-            hint_string,
-        } = T::export_info(self.hint);
+        let export_info = T::export_info(self.hint);
         let default = self.default.to_variant();
 
-        let mut attr = sys::godot_property_attributes {
-            rset_type: sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_DISABLED, // TODO:
-            type_: variant_type as sys::godot_int,
-            hint: hint_kind,
-            hint_string: hint_string.to_sys(),
-            usage: self.usage.to_sys(),
-            default_value: default.to_sys(),
-        };
-
-        let path = ::std::ffi::CString::new(self.name).unwrap();
-
-        let set = unsafe { self.setter.into_godot_function() };
+        let mut set = unsafe { self.setter.into_godot_function() };
         let get = unsafe { self.getter.into_godot_function() };
 
-        unsafe {
-            (get_api().godot_nativescript_register_property)(
-                self.class_builder.init_handle,
-                self.class_builder.class_name.as_ptr(),
-                path.as_ptr() as *const _,
-                &mut attr,
-                set,
-                get,
-            );
+        if !self.hooks.is_empty() {
+            set = unsafe { self.hooks.wrap::<C>(self.name, set, get, G::IS_VALID) };
         }
+
+        register_raw(
+            self.class_builder,
+            self.name,
+            export_info,
+            self.usage,
+            default,
+            set,
+            get,
+            self.documentation,
+        );
     }
 
     /// Provides a setter function with the signature `fn(&mut C, owner: C::Base, value: T)`
@@ -151,11 +202,13 @@ where
     {
         PropertyBuilder {
             name: self.name,
-            setter: Setter::new(setter),
+            setter: Setter::new(self.name, setter),
             getter: self.getter,
             default: self.default,
             hint: self.hint,
             usage: self.usage,
+            hooks: self.hooks,
+            documentation: self.documentation,
             class_builder: self.class_builder,
         }
     }
@@ -174,11 +227,32 @@ where
     {
         PropertyBuilder {
             name: self.name,
-            setter: Setter::new(setter),
+            setter: Setter::new(self.name, setter),
             getter: self.getter,
             default: self.default,
             hint: self.hint,
             usage: self.usage,
+            hooks: self.hooks,
+            documentation: self.documentation,
+            class_builder: self.class_builder,
+        }
+    }
+
+    /// Provides a getter that always returns `value`, without accessing the script instance.
+    #[inline]
+    pub(crate) fn with_const_getter(self, value: T) -> PropertyBuilder<'a, C, T, S, ConstGetter<T>>
+    where
+        ConstGetter<T>: RawGetter<C, T>,
+    {
+        PropertyBuilder {
+            name: self.name,
+            setter: self.setter,
+            getter: ConstGetter::new(value),
+            default: self.default,
+            hint: self.hint,
+            usage: self.usage,
+            hooks: self.hooks,
+            documentation: self.documentation,
             class_builder: self.class_builder,
         }
     }
@@ -200,6 +274,8 @@ where
             default: self.default,
             hint: self.hint,
             usage: self.usage,
+            hooks: self.hooks,
+            documentation: self.documentation,
             class_builder: self.class_builder,
         }
     }
@@ -221,6 +297,8 @@ where
             default: self.default,
             hint: self.hint,
             usage: self.usage,
+            hooks: self.hooks,
+            documentation: self.documentation,
             class_builder: self.class_builder,
         }
     }
@@ -242,6 +320,8 @@ where
             default: self.default,
             hint: self.hint,
             usage: self.usage,
+            hooks: self.hooks,
+            documentation: self.documentation,
             class_builder: self.class_builder,
         }
     }
@@ -263,6 +343,8 @@ where
             default: self.default,
             hint: self.hint,
             usage: self.usage,
+            hooks: self.hooks,
+            documentation: self.documentation,
             class_builder: self.class_builder,
         }
     }
@@ -288,6 +370,160 @@ where
         self.usage = usage;
         self
     }
+
+    /// Provides a validator that is called with each new value before the setter. The
+    /// validator may return a modified value, which is passed to the setter instead. If it
+    /// returns an error, a warning is printed and the setter is not called, leaving the
+    /// property unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// builder
+    ///     .property("health")
+    ///     .with_setter(MyClass::set_health)
+    ///     .with_validator(|health: i64| {
+    ///         if health < 0 {
+    ///             Err(format!("health must not be negative, got {}", health))
+    ///         } else {
+    ///             Ok(health.min(100))
+    ///         }
+    ///     })
+    ///     .done();
+    /// ```
+    #[inline]
+    pub fn with_validator<V>(mut self, validator: V) -> Self
+    where
+        T: FromVariant + 'static,
+        V: Fn(T) -> Result<T, String> + Send + Sync + 'static,
+    {
+        self.hooks.validator = Some(SetterValidator::new(move |value: &Variant| {
+            let value = T::from_variant(value).map_err(|err| err.to_string())?;
+            validator(value).map(|value| value.to_variant())
+        }));
+        self
+    }
+
+    /// Emits the signal `signal` on the owner object whenever the value of the property is
+    /// changed through its setter. The signal is emitted with the new value as its only
+    /// argument, and should be registered on the class with
+    /// [`ClassBuilder::signal`](crate::export::ClassBuilder::signal).
+    ///
+    /// If the property has a getter, it is used to skip notifications when the value stays the
+    /// same. Otherwise, the signal is emitted after every call to the setter.
+    #[inline]
+    pub fn with_notify(mut self, signal: &str) -> Self {
+        self.hooks.notify = Some(GodotString::from_str(signal));
+        self
+    }
+
+    /// Sets the documentation of this property, which is shown in the editor and included in
+    /// [`class_reference_xml`](crate::export::class_reference_xml).
+    #[inline]
+    pub fn with_documentation(mut self, documentation: &'a str) -> Self {
+        self.documentation = Some(documentation);
+        self
+    }
+}
+
+/// Registers an inspector header (a group or a category) on the class being registered.
+///
+/// Headers are entries without a value. The inspector tells them apart from regular
+/// properties through their usage flags.
+pub(super) fn register_header<C: NativeClass>(
+    class_builder: &ClassBuilder<C>,
+    name: &str,
+    hint_string: &str,
+    usage: PropertyUsage,
+) {
+    let export_info = ExportInfo {
+        variant_type: VariantType::Nil,
+        hint_kind: sys::godot_property_hint_GODOT_PROPERTY_HINT_NONE,
+        hint_string: hint_string.into(),
+    };
+
+    let set = unsafe { RawSetter::<C, Variant>::into_godot_function(InvalidSetter::new(name)) };
+    let get = unsafe { RawGetter::<C, Variant>::into_godot_function(InvalidGetter::new(name)) };
+
+    register_raw(
+        class_builder,
+        name,
+        export_info,
+        usage,
+        Variant::nil(),
+        set,
+        get,
+        None,
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+fn register_raw<C: NativeClass>(
+    class_builder: &ClassBuilder<C>,
+    name: &str,
+    export_info: ExportInfo,
+    usage: PropertyUsage,
+    default: Variant,
+    set: sys::godot_property_set_func,
+    get: sys::godot_property_get_func,
+    documentation: Option<&str>,
+) {
+    // Inspector headers are not members of the class
+    if !usage.intersects(PropertyUsage::GROUP | PropertyUsage::CATEGORY) {
+        super::class_docs::record_property(
+            class_builder.class_type,
+            name,
+            &export_info,
+            &default,
+            documentation,
+        );
+    }
+
+    let ExportInfo {
+        variant_type,
+        hint_kind,
+        hint_string,
+    } = export_info;
+
+    let mut attr = sys::godot_property_attributes {
+        rset_type: sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_DISABLED, // TODO:
+        type_: variant_type as sys::godot_int,
+        hint: hint_kind,
+        hint_string: hint_string.to_sys(),
+        usage: usage.to_sys(),
+        default_value: default.to_sys(),
+    };
+
+    let path = ::std::ffi::CString::new(name).unwrap();
+
+    let (set, get) = if class_builder.upcasts.is_empty() {
+        (set, get)
+    } else {
+        (
+            super::super_class::upcast_setter(&class_builder.upcasts, set),
+            super::super_class::upcast_getter(&class_builder.upcasts, get),
+        )
+    };
+
+    unsafe {
+        (get_api().godot_nativescript_register_property)(
+            class_builder.init_handle,
+            class_builder.class_name.as_ptr(),
+            path.as_ptr() as *const _,
+            &mut attr,
+            set,
+            get,
+        );
+
+        if let Some(documentation) = documentation {
+            (get_api().godot_nativescript_set_property_documentation)(
+                class_builder.init_handle,
+                class_builder.class_name.as_ptr(),
+                path.as_ptr() as *const _,
+                GodotString::from(documentation).to_sys(),
+            );
+        }
+    }
 }
 
 bitflags::bitflags! {
@@ -529,9 +765,7 @@ mod impl_export {
     impl_export_for_core_type_without_hint!(Aabb);
     impl_export_for_core_type_without_hint!(Basis);
     impl_export_for_core_type_without_hint!(Transform);
-    impl_export_for_core_type_without_hint!(NodePath);
     impl_export_for_core_type_without_hint!(Rid);
-    impl_export_for_core_type_without_hint!(Dictionary);
     impl_export_for_core_type_without_hint!(ByteArray);
     impl_export_for_core_type_without_hint!(Int32Array);
     impl_export_for_core_type_without_hint!(Float32Array);
@@ -540,6 +774,15 @@ mod impl_export {
     impl_export_for_core_type_without_hint!(Vector3Array);
     impl_export_for_core_type_without_hint!(ColorArray);
 
+    /// `Variant` is exported as an untyped value, which is represented as `Nil` in Godot.
+    impl Export for Variant {
+        type Hint = NoHint;
+        #[inline]
+        fn export_info(_hint: Option<Self::Hint>) -> ExportInfo {
+            ExportInfo::new(VariantType::Nil)
+        }
+    }
+
     impl Export for Color {
         type Hint = hint::ColorHint;
         #[inline]
@@ -562,6 +805,18 @@ mod impl_export {
         }
     }
 
+    /// Exported as a resource of the script class `T`. The editor then only accepts resources
+    /// using the `.gdns` script of `T`, as long as it is declared as a global script class with
+    /// `script_class_name`, e.g. with
+    /// [`ScriptResources::with_script_class`](crate::init::ScriptResources::with_script_class).
+    ///
+    /// The name of the script class is only known once `T` is registered, so `T` must be added
+    /// to the `InitHandle` before the classes exporting such properties, unless all of them are
+    /// added with `InitHandle::add_all_classes`. Otherwise, any resource of the base type is
+    /// accepted.
+    ///
+    /// Values of the wrong type are rejected by the property setter with a warning naming the
+    /// expected class.
     impl<T> Export for Instance<T, Shared>
     where
         T: NativeClass,
@@ -570,7 +825,14 @@ mod impl_export {
         type Hint = NoHint;
         #[inline]
         fn export_info(_hint: Option<Self::Hint>) -> ExportInfo {
-            ExportInfo::resource_type::<T::Base>()
+            match class_registry::registered_or_declared_name::<T>() {
+                Some(class_name) => ExportInfo {
+                    variant_type: VariantType::Object,
+                    hint_kind: sys::godot_property_hint_GODOT_PROPERTY_HINT_RESOURCE_TYPE,
+                    hint_string: GodotString::from_str(class_name),
+                },
+                None => ExportInfo::resource_type::<T::Base>(),
+            }
         }
     }
 
@@ -585,6 +847,27 @@ mod impl_export {
         }
     }
 
+    impl Export for NodePath {
+        type Hint = hint::NodePathHint;
+
+        #[inline]
+        fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+            hint.map_or_else(
+                || ExportInfo::new(VariantType::NodePath),
+                Self::Hint::export_info,
+            )
+        }
+    }
+
+    impl Export for Dictionary<Shared> {
+        type Hint = hint::DictionaryHint;
+
+        #[inline]
+        fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+            hint.unwrap_or_default().export_info()
+        }
+    }
+
     impl Export for VariantArray<Shared> {
         type Hint = hint::ArrayHint;
 
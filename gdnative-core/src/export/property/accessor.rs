@@ -20,20 +20,26 @@ pub unsafe trait RawSetter<C, T> {
 ///
 /// This is an internal interface. User code should not use this directly.
 pub unsafe trait RawGetter<C, T> {
+    /// Whether this is a getter that actually returns the property value.
+    #[doc(hidden)]
+    const IS_VALID: bool = true;
+
     #[doc(hidden)]
     unsafe fn into_godot_function(self) -> sys::godot_property_get_func;
 }
 
 #[derive(Debug)]
 pub struct Setter<SelfArg, F> {
+    property_name: String,
     func: F,
     _self_arg: PhantomData<SelfArg>,
 }
 
 impl<SelfArg, F> Setter<SelfArg, F> {
     #[inline]
-    pub fn new(func: F) -> Self {
+    pub fn new(property_name: &str, func: F) -> Self {
         Setter {
+            property_name: property_name.to_owned(),
             func,
             _self_arg: PhantomData,
         }
@@ -58,6 +64,20 @@ impl<SelfArg, RetKind, F> Getter<SelfArg, RetKind, F> {
     }
 }
 
+/// Getter that always returns the same value, without accessing the script instance. Used for
+/// constants registered with [`ClassBuilder::constant`](crate::export::ClassBuilder::constant).
+#[derive(Debug)]
+pub struct ConstGetter<T> {
+    value: T,
+}
+
+impl<T> ConstGetter<T> {
+    #[inline]
+    pub fn new(value: T) -> Self {
+        ConstGetter { value }
+    }
+}
+
 /// Marker type for accessors that take `&self` as their first arguments.
 pub struct Shr;
 /// Marker type for accessors that take `&mut self` as their first arguments.
@@ -203,7 +223,7 @@ where
     #[inline]
     unsafe fn into_godot_function(self) -> sys::godot_property_set_func {
         let mut set = sys::godot_property_set_func::default();
-        let data = Box::new(self.func);
+        let data = Box::new((self.func, self.property_name));
         set.method_data = Box::into_raw(data) as *mut _;
 
         extern "C" fn invoke<SelfArg, C, F, T>(
@@ -238,7 +258,7 @@ where
             let result = std::panic::catch_unwind(|| unsafe {
                 let user_data = C::UserData::clone_from_user_data_unchecked(class as *const _);
                 let owner = TRef::new(C::Base::cast_ref(RawObject::from_sys_ref_unchecked(this)));
-                let func = &*(method as *const F);
+                let (func, property_name) = &*(method as *const (F, String));
 
                 match T::from_variant(Variant::cast_ref(val)) {
                     Ok(val) => {
@@ -247,7 +267,12 @@ where
                         }
                     }
                     Err(err) => {
-                        godot_error!("Incorrect type passed to property: {}", err);
+                        godot_warn!(
+                            "gdnative-core: incorrect type passed to property `{}` of {}: {}",
+                            property_name,
+                            class_registry::class_name_or_default::<C>(),
+                            err,
+                        );
                     }
                 }
             });
@@ -260,7 +285,7 @@ where
 
         extern "C" fn free_func<F>(data: *mut libc::c_void) {
             unsafe {
-                drop(Box::from_raw(data as *mut F));
+                drop(Box::from_raw(data as *mut (F, String)));
             }
         }
         set.free_func = Some(free_func::<F>);
@@ -341,3 +366,42 @@ where
         get
     }
 }
+
+unsafe impl<C, T> RawGetter<C, T> for ConstGetter<T>
+where
+    C: NativeClass,
+    T: ToVariant + 'static,
+{
+    #[inline]
+    unsafe fn into_godot_function(self) -> sys::godot_property_get_func {
+        let mut get = sys::godot_property_get_func::default();
+        let data = Box::new(self.value);
+        get.method_data = Box::into_raw(data) as *mut _;
+
+        extern "C" fn invoke<T: ToVariant>(
+            _this: *mut sys::godot_object,
+            method: *mut libc::c_void,
+            _class: *mut libc::c_void,
+        ) -> sys::godot_variant {
+            let result = std::panic::catch_unwind(|| unsafe {
+                let value = &*(method as *const T);
+                value.to_variant().leak()
+            });
+
+            result.unwrap_or_else(|_| {
+                godot_error!("gdnative-core: constant getter panicked (check stderr for output)");
+                Variant::nil().leak()
+            })
+        }
+        get.get_func = Some(invoke::<T>);
+
+        extern "C" fn free_func<T>(data: *mut libc::c_void) {
+            unsafe {
+                drop(Box::from_raw(data as *mut T));
+            }
+        }
+        get.free_func = Some(free_func::<T>);
+
+        get
+    }
+}
@@ -1,14 +1,21 @@
 //! Strongly typed property hints.
 
+use std::convert::TryFrom;
 use std::fmt::{self, Write};
 use std::ops::RangeInclusive;
 
 use crate::core_types::GodotString;
 use crate::core_types::VariantType;
+use crate::object::GodotObject;
 use crate::sys;
 
 use super::{Export, ExportInfo};
 
+/// `PROPERTY_HINT_NODE_PATH_VALID_TYPES`, which is missing from the GDNative headers. In the
+/// engine, it follows `PROPERTY_HINT_PROPERTY_OF_SCRIPT` and `PROPERTY_HINT_OBJECT_TOO_BIG`.
+const PROPERTY_HINT_NODE_PATH_VALID_TYPES: sys::godot_property_hint =
+    sys::godot_property_hint_GODOT_PROPERTY_HINT_PROPERTY_OF_SCRIPT + 2;
+
 /// Hints that an integer or float property should be within an inclusive range.
 ///
 /// # Examples
@@ -125,6 +132,26 @@ impl EnumHint {
         EnumHint { values }
     }
 
+    /// Creates an `EnumHint` for an integer property with explicit values, instead of
+    /// consecutive values starting from zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gdnative_core::export::hint::EnumHint;
+    ///
+    /// // Shows "Idle" and "Running" in the inspector, with the values 0 and 10
+    /// let hint = EnumHint::with_numbers(vec![("Idle".into(), 0), ("Running".into(), 10)]);
+    /// ```
+    #[inline]
+    pub fn with_numbers(values: Vec<(String, i64)>) -> Self {
+        let values = values
+            .into_iter()
+            .map(|(name, value)| format!("{}:{}", name, value))
+            .collect();
+        EnumHint { values }
+    }
+
     /// Formats the hint as a Godot hint string.
     fn to_godot_hint_string(&self) -> GodotString {
         let mut s = String::new();
@@ -143,6 +170,82 @@ impl EnumHint {
     }
 }
 
+/// Hints that an integer property is a bitmask with named bit flags. The `n`-th name is the
+/// name of the bit `1 << n`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use gdnative_core::export::hint::{FlagsHint, IntHint};
+///
+/// let hint: IntHint<u32> = FlagsHint::new(vec!["Fire".into(), "Water".into()]).into();
+/// ```
+///
+/// Using the names of a type generated by the `bitflags` crate:
+///
+/// ```rust
+/// use gdnative_core::export::hint::FlagsHint;
+///
+/// bitflags::bitflags! {
+///     struct Elements: u32 {
+///         const FIRE = 1;
+///         const WATER = 1 << 1;
+///         const EARTH = 1 << 3;
+///     }
+/// }
+///
+/// // Names are "FIRE", "WATER", "" and "EARTH"
+/// let hint = FlagsHint::from_bitflags(Elements::from_bits);
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct FlagsHint {
+    names: Vec<String>,
+}
+
+impl FlagsHint {
+    /// Creates a `FlagsHint` from the names of the bits, starting from the lowest one.
+    #[inline]
+    pub fn new(names: Vec<String>) -> Self {
+        FlagsHint { names }
+    }
+
+    /// Creates a `FlagsHint` from a function that converts bits to a bit flags type, like the
+    /// `from_bits` function generated by the `bitflags` crate. The name of each bit is the
+    /// `Debug` representation of the corresponding value. Bits for which `from_bits` returns
+    /// `None` are left unnamed.
+    #[inline]
+    pub fn from_bitflags<F, B>(from_bits: impl Fn(B) -> Option<F>) -> Self
+    where
+        F: fmt::Debug,
+        B: TryFrom<u64>,
+    {
+        let mut names = Vec::new();
+        for n in 0..64 {
+            let bit = match B::try_from(1u64 << n) {
+                Ok(bit) => bit,
+                Err(_) => break,
+            };
+            let name = from_bits(bit).map(|flag| format!("{:?}", flag));
+            names.push(name.unwrap_or_default());
+        }
+
+        while names.last().map_or(false, String::is_empty) {
+            names.pop();
+        }
+
+        FlagsHint { names }
+    }
+}
+
+impl From<FlagsHint> for EnumHint {
+    #[inline]
+    fn from(hint: FlagsHint) -> Self {
+        EnumHint::new(hint.names)
+    }
+}
+
 /// Possible hints for integers.
 #[derive(Clone, Debug)]
 pub enum IntHint<T> {
@@ -224,6 +327,13 @@ impl<T> From<EnumHint> for IntHint<T> {
     }
 }
 
+impl<T> From<FlagsHint> for IntHint<T> {
+    #[inline]
+    fn from(hint: FlagsHint) -> Self {
+        Self::Flags(hint.into())
+    }
+}
+
 /// Hints that a float property should be edited via an exponential easing function.
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
 pub struct ExpEasingHint {
@@ -371,6 +481,47 @@ impl StringHint {
     }
 }
 
+/// Hints for `NodePath`, restricting the nodes that can be selected in the editor.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use gdnative::api::{Camera2D, Node2D};
+/// use gdnative::export::hint::NodePathHint;
+///
+/// let hint = NodePathHint::valid_types::<Node2D>().with_valid_type::<Camera2D>();
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct NodePathHint {
+    valid_types: Vec<String>,
+}
+
+impl NodePathHint {
+    /// Returns a `NodePathHint` that only allows nodes of type `T`, or types inheriting it.
+    #[inline]
+    pub fn valid_types<T: GodotObject>() -> Self {
+        Self::default().with_valid_type::<T>()
+    }
+
+    /// Builder-style method that returns `self` with `T` as an additional valid type.
+    #[inline]
+    pub fn with_valid_type<T: GodotObject>(mut self) -> Self {
+        self.valid_types.push(T::class_name().to_string());
+        self
+    }
+
+    #[inline]
+    pub fn export_info(self) -> ExportInfo {
+        ExportInfo {
+            variant_type: VariantType::NodePath,
+            hint_kind: PROPERTY_HINT_NODE_PATH_VALID_TYPES,
+            hint_string: self.valid_types.join(",").into(),
+        }
+    }
+}
+
 /// Possible hints for `Color`.
 #[derive(Clone, Debug)]
 pub enum ColorHint {
@@ -430,38 +581,119 @@ impl ArrayHint {
 impl ArrayHint {
     #[inline]
     pub fn export_info(self) -> ExportInfo {
-        if let Some(element_hint) = self.element_hint {
-            let hint_string = match (element_hint.variant_type, element_hint.hint_kind) {
-                // Special-cased because sub-hints seem to leave off the hint only if it's NONE,
-                // but Array will also do it on HINT_TYPE_STRING.
-                (
-                    VariantType::VariantArray,
-                    sys::godot_property_hint_GODOT_PROPERTY_HINT_TYPE_STRING,
-                ) => format!(
-                    "{}:{}",
-                    VariantType::VariantArray as u32,
-                    element_hint.hint_string
-                ),
-                (variant_type, sys::godot_property_hint_GODOT_PROPERTY_HINT_NONE) => {
-                    format!("{}:{}", variant_type as u32, element_hint.hint_string)
-                }
-                (variant_type, hint_type) => format!(
-                    "{}/{}:{}",
-                    variant_type as u32, hint_type, element_hint.hint_string
-                ),
-            }
-            .into();
-            ExportInfo {
-                variant_type: VariantType::VariantArray,
-                hint_kind: sys::godot_property_hint_GODOT_PROPERTY_HINT_TYPE_STRING,
-                hint_string,
+        typed_collection_export_info(VariantType::VariantArray, self.element_hint)
+    }
+}
+
+/// Dictionary hints optionally with a value hint.
+///
+/// Godot 3 has no typed dictionaries, so the editor does not enforce the value type. It is
+/// reported in the property list, e.g. for tools and plugins.
+#[derive(Debug, Default)]
+pub struct DictionaryHint {
+    value_hint: Option<ExportInfo>,
+}
+
+impl DictionaryHint {
+    /// Returns a `DictionaryHint` without a value hint.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a `DictionaryHint` with a value hint for type `T`, but without a hint for
+    /// that type.
+    #[inline]
+    pub fn with_value<T: Export>() -> Self {
+        Self::with_maybe_value_hint::<T>(None)
+    }
+
+    /// Returns a `DictionaryHint` with a value hint for type `T`, and a hint for that type.
+    #[inline]
+    pub fn with_value_hint<T: Export>(hint: T::Hint) -> Self {
+        Self::with_maybe_value_hint::<T>(Some(hint))
+    }
+
+    /// Returns a `DictionaryHint` with a value hint for type `T`, and optionally a hint
+    /// for that type.
+    #[inline]
+    pub fn with_maybe_value_hint<T: Export>(hint: Option<T::Hint>) -> Self {
+        DictionaryHint {
+            value_hint: Some(T::export_info(hint)),
+        }
+    }
+
+    #[inline]
+    pub fn export_info(self) -> ExportInfo {
+        typed_collection_export_info(VariantType::Dictionary, self.value_hint)
+    }
+}
+
+/// Returns the `ExportInfo` of a collection with elements described by `element_hint`, using
+/// the same hint string format as typed arrays.
+fn typed_collection_export_info(
+    variant_type: VariantType,
+    element_hint: Option<ExportInfo>,
+) -> ExportInfo {
+    if let Some(element_hint) = element_hint {
+        let hint_string = match (element_hint.variant_type, element_hint.hint_kind) {
+            // Special-cased because sub-hints seem to leave off the hint only if it's NONE,
+            // but Array will also do it on HINT_TYPE_STRING.
+            (
+                VariantType::VariantArray,
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_TYPE_STRING,
+            ) => format!(
+                "{}:{}",
+                VariantType::VariantArray as u32,
+                element_hint.hint_string
+            ),
+            (variant_type, sys::godot_property_hint_GODOT_PROPERTY_HINT_NONE) => {
+                format!("{}:{}", variant_type as u32, element_hint.hint_string)
             }
-        } else {
-            ExportInfo {
-                variant_type: VariantType::VariantArray,
-                hint_kind: sys::godot_property_hint_GODOT_PROPERTY_HINT_NONE,
-                hint_string: GodotString::new(),
+            (variant_type, hint_type) => format!(
+                "{}/{}:{}",
+                variant_type as u32, hint_type, element_hint.hint_string
+            ),
+        }
+        .into();
+        ExportInfo {
+            variant_type,
+            hint_kind: sys::godot_property_hint_GODOT_PROPERTY_HINT_TYPE_STRING,
+            hint_string,
+        }
+    } else {
+        ExportInfo {
+            variant_type,
+            hint_kind: sys::godot_property_hint_GODOT_PROPERTY_HINT_NONE,
+            hint_string: GodotString::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_path_valid_types_follows_property_of_script() {
+        // `PROPERTY_HINT_NODE_PATH_VALID_TYPES` in Godot's `PropertyHint` enum
+        assert_eq!(35, PROPERTY_HINT_NODE_PATH_VALID_TYPES);
+    }
+
+    #[test]
+    fn flags_from_bitflags() {
+        bitflags::bitflags! {
+            struct Elements: u8 {
+                const FIRE = 1;
+                const WATER = 1 << 1;
+                const EARTH = 1 << 3;
             }
         }
+
+        let hint = FlagsHint::from_bitflags(Elements::from_bits);
+        assert_eq!(vec!["FIRE", "WATER", "", "EARTH"], hint.names);
+
+        let hint = FlagsHint::from_bitflags(|_: u8| None::<Elements>);
+        assert!(hint.names.is_empty());
     }
 }
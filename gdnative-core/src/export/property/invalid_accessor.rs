@@ -96,6 +96,8 @@ unsafe impl<'l, C: NativeClass, T: FromVariant> RawSetter<C, T> for InvalidSette
 }
 
 unsafe impl<'l, C: NativeClass, T: ToVariant> RawGetter<C, T> for InvalidGetter<'l> {
+    const IS_VALID: bool = false;
+
     #[inline]
     unsafe fn into_godot_function(self) -> sys::godot_property_get_func {
         let mut get = sys::godot_property_get_func::default();
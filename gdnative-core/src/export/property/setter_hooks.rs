@@ -0,0 +1,180 @@
+//! Validation and change notifications around property setters.
+
+use std::fmt;
+
+use crate::core_types::{GodotString, ToVariant, Variant};
+use crate::export::{class_registry, NativeClass};
+use crate::private::{get_api, ObjectMethodTable};
+
+type ValidatorFn = dyn Fn(&Variant) -> Result<Variant, String> + Send + Sync;
+
+/// Validator for values passed to a property setter, operating on the `Variant` representation.
+pub(super) struct SetterValidator(Box<ValidatorFn>);
+
+impl SetterValidator {
+    pub(super) fn new<F>(validator: F) -> Self
+    where
+        F: Fn(&Variant) -> Result<Variant, String> + Send + Sync + 'static,
+    {
+        SetterValidator(Box::new(validator))
+    }
+}
+
+impl fmt::Debug for SetterValidator {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SetterValidator")
+    }
+}
+
+/// Additional behavior around a property setter, applied when the property is registered.
+#[derive(Debug, Default)]
+pub(super) struct SetterHooks {
+    pub(super) validator: Option<SetterValidator>,
+    pub(super) notify: Option<GodotString>,
+}
+
+impl SetterHooks {
+    pub(super) fn is_empty(&self) -> bool {
+        self.validator.is_none() && self.notify.is_none()
+    }
+
+    /// Wraps `set`, so that values are validated before it is called, and change notifications
+    /// are sent after it returns. If `detect_changes` is true, `get` is used to compare the
+    /// property values before and after the setter. Otherwise, notifications are sent after
+    /// every call.
+    ///
+    /// # Safety
+    ///
+    /// `set` and `get` must be valid accessors for the same property. `get` must outlive the
+    /// returned setter.
+    pub(super) unsafe fn wrap<C: NativeClass>(
+        self,
+        property_name: &str,
+        set: sys::godot_property_set_func,
+        get: sys::godot_property_get_func,
+        detect_changes: bool,
+    ) -> sys::godot_property_set_func {
+        let data = Box::new(HookedSetter {
+            property_name: property_name.to_string(),
+            set,
+            get: if detect_changes { Some(get) } else { None },
+            hooks: self,
+        });
+
+        let mut wrapped = sys::godot_property_set_func::default();
+        wrapped.method_data = Box::into_raw(data) as *mut _;
+        wrapped.set_func = Some(invoke::<C>);
+        wrapped.free_func = Some(free_func);
+        wrapped
+    }
+}
+
+struct HookedSetter {
+    property_name: String,
+    set: sys::godot_property_set_func,
+    get: Option<sys::godot_property_get_func>,
+    hooks: SetterHooks,
+}
+
+impl HookedSetter {
+    unsafe fn get_value(
+        &self,
+        this: *mut sys::godot_object,
+        class: *mut libc::c_void,
+    ) -> Option<Variant> {
+        let get = self.get.as_ref()?;
+        let get_func = get.get_func?;
+        Some(Variant::from_sys(get_func(this, get.method_data, class)))
+    }
+}
+
+impl Drop for HookedSetter {
+    fn drop(&mut self) {
+        // The wrapped setter is owned by this one. The getter is registered separately.
+        if let Some(free_func) = self.set.free_func {
+            unsafe { free_func(self.set.method_data) };
+        }
+    }
+}
+
+extern "C" fn invoke<C: NativeClass>(
+    this: *mut sys::godot_object,
+    method: *mut libc::c_void,
+    class: *mut libc::c_void,
+    val: *mut sys::godot_variant,
+) {
+    let result = std::panic::catch_unwind(|| unsafe {
+        let data = &*(method as *const HookedSetter);
+        let set_func = match data.set.set_func {
+            Some(set_func) => set_func,
+            None => return,
+        };
+
+        let validated;
+        let val = match &data.hooks.validator {
+            Some(SetterValidator(validator)) => match validator(Variant::cast_ref(val)) {
+                Ok(value) => {
+                    validated = value;
+                    validated.sys() as *mut sys::godot_variant
+                }
+                Err(err) => {
+                    godot_warn!(
+                        "invalid value for property {} on native class {}: {}",
+                        data.property_name,
+                        class_registry::class_name_or_default::<C>(),
+                        err,
+                    );
+                    return;
+                }
+            },
+            None => val,
+        };
+
+        let signal = match &data.hooks.notify {
+            Some(signal) => signal,
+            None => {
+                set_func(this, data.set.method_data, class, val);
+                return;
+            }
+        };
+
+        let old = data.get_value(this, class);
+        set_func(this, data.set.method_data, class, val);
+        let new = data.get_value(this, class);
+
+        if old.is_none() || old != new {
+            let value = new.unwrap_or_else(|| Variant::cast_ref(val).clone());
+            emit_signal(this, signal, &value);
+        }
+    });
+
+    result.unwrap_or_else(|_| {
+        godot_error!("gdnative-core: property setter panicked (check stderr for output)");
+    })
+}
+
+extern "C" fn free_func(data: *mut libc::c_void) {
+    unsafe {
+        drop(Box::from_raw(data as *mut HookedSetter));
+    }
+}
+
+/// Emits `signal` on `this`, with `value` as the only argument.
+unsafe fn emit_signal(this: *mut sys::godot_object, signal: &GodotString, value: &Variant) {
+    let api = get_api();
+    let method_bind = ObjectMethodTable::get(api).emit_signal;
+
+    let signal = signal.to_variant();
+    let mut args = [signal.sys(), value.sys()];
+    let mut err = sys::godot_variant_call_error::default();
+
+    let ret = (api.godot_method_bind_call)(
+        method_bind,
+        this,
+        args.as_mut_ptr(),
+        args.len() as libc::c_int,
+        &mut err,
+    );
+    drop(Variant::from_sys(ret));
+}
@@ -1,5 +1,8 @@
+use std::fmt;
+use std::marker::PhantomData;
+
 use crate::core_types::{GodotString, Variant, VariantType};
-use crate::export::{ClassBuilder, ExportInfo, NativeClass, PropertyUsage};
+use crate::export::{ClassBuilder, Export, ExportInfo, NativeClass, PropertyUsage};
 
 /// Class to construct a signal. Make sure to call [`Self::done()`] in the end.
 ///
@@ -12,6 +15,7 @@ pub struct SignalBuilder<'a, C> {
     class_builder: &'a ClassBuilder<C>,
     name: GodotString,
     args: Vec<SignalParam>,
+    documentation: Option<GodotString>,
 }
 
 impl<'a, C: NativeClass> SignalBuilder<'a, C> {
@@ -20,6 +24,7 @@ impl<'a, C: NativeClass> SignalBuilder<'a, C> {
             class_builder,
             name: signal_name,
             args: vec![],
+            documentation: None,
         }
     }
 
@@ -37,6 +42,35 @@ impl<'a, C: NativeClass> SignalBuilder<'a, C> {
         })
     }
 
+    /// Add a parameter for the signal with a name, and a type inferred from the Rust type `T`.
+    ///
+    /// The Variant type, hint and hint string are taken from [`Export::export_info`], so that e.g.
+    /// a `Ref<Texture>` parameter shows up as a `Texture` in the editor's signal connection dialog.
+    /// Note that GDScript signal parameters are generally untyped and not checked at runtime.
+    /// The type is solely used for UI purposes.
+    #[inline]
+    pub fn with_typed_param<T: Export>(self, parameter_name: &str) -> Self {
+        self.with_typed_param_hint::<T>(parameter_name, None)
+    }
+
+    /// Add a parameter for the signal with a name, and a type inferred from the Rust type `T`
+    /// together with an optional hint for that type.
+    ///
+    /// See [`Self::with_typed_param()`] for details.
+    #[inline]
+    pub fn with_typed_param_hint<T: Export>(
+        self,
+        parameter_name: &str,
+        hint: Option<T::Hint>,
+    ) -> Self {
+        self.with_param_custom(SignalParam {
+            name: parameter_name.into(),
+            default: Variant::nil(),
+            export_info: T::export_info(hint),
+            usage: PropertyUsage::DEFAULT,
+        })
+    }
+
     /// Add a parameter for the signal with a name and default value.
     ///
     /// The type is inferred from the default value.
@@ -76,19 +110,29 @@ impl<'a, C: NativeClass> SignalBuilder<'a, C> {
         self
     }
 
+    /// Sets the documentation of this signal, which is shown in the editor and included in
+    /// [`class_reference_xml`](crate::export::class_reference_xml).
+    #[inline]
+    pub fn with_documentation(mut self, documentation: &str) -> Self {
+        self.documentation = Some(documentation.into());
+        self
+    }
+
     /// Finish registering the signal.
     #[inline]
     pub fn done(self) {
-        self.class_builder.add_signal(Signal {
+        self.class_builder.add_signal(ScriptSignal {
             name: self.name,
             args: self.args,
+            documentation: self.documentation,
         });
     }
 }
 
-pub(crate) struct Signal {
+pub(crate) struct ScriptSignal {
     pub name: GodotString,
     pub args: Vec<SignalParam>,
+    pub documentation: Option<GodotString>,
 }
 
 /// Parameter in a signal declaration.
@@ -101,9 +145,88 @@ pub struct SignalParam {
     /// Default value, used when no argument is provided.
     pub default: Variant,
 
-    /// Metadata and UI hints about exporting, e.g. parameter type.
+    /// Metadata and UI hints about exporting, e.g. parameter type, hint kind and hint string.
+    ///
+    /// For parameters added with [`SignalBuilder::with_typed_param()`], this is obtained from
+    /// the [`Export`] implementation of the parameter's Rust type.
     pub export_info: ExportInfo,
 
     /// In which context the signal parameter is used.
     pub usage: PropertyUsage,
 }
+
+/// Placeholder type for signals declared as fields of a `NativeClass`.
+///
+/// A field of type `Signal<Args>` attributed with `#[signal]` registers a signal whose parameter
+/// types are given by `Args`, which is either a tuple of parameter types or `()` for signals
+/// without parameters. The `NativeClass` derive macro then generates typed `emit_<field>` and
+/// `connect_<field>` associated functions on the class, so that the signal name and its argument
+/// types are checked by the compiler instead of at runtime.
+///
+/// Like [`Property`][crate::export::Property], `Signal` is a zero-sized type (ZST) which has exactly
+/// one value: `Signal::default()`. It does not hold any state; the signal itself is owned by the
+/// base object.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use gdnative::prelude::*;
+/// use gdnative::export::Signal;
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// #[no_constructor]
+/// struct Player {
+///     #[signal(params = "height, direction")]
+///     jumped: Signal<(i64, Vector2)>,
+///
+///     #[signal]
+///     landed: Signal<()>,
+/// }
+///
+/// #[methods]
+/// impl Player {
+///     #[method]
+///     fn jump(&self, #[base] base: &Node) {
+///         // Compile error if the signal is renamed or the argument types change
+///         Self::emit_jumped(base, 42, Vector2::UP);
+///     }
+/// }
+/// ```
+pub struct Signal<Args> {
+    _marker: PhantomData<fn() -> Args>,
+}
+
+impl<Args> Signal<Args> {
+    /// Creates a new `Signal` placeholder. Equivalent to `Signal::default()`.
+    #[inline]
+    pub fn new() -> Self {
+        Signal {
+            _marker: PhantomData,
+        }
+    }
+}
+
+// Note: traits are implemented manually, since deriving them would impose bounds on `Args`.
+impl<Args> Default for Signal<Args> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Args> Clone for Signal<Args> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<Args> Copy for Signal<Args> {}
+
+impl<Args> fmt::Debug for Signal<Args> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Signal<{}>", std::any::type_name::<Args>())
+    }
+}
@@ -0,0 +1,377 @@
+//! Inheritance between `NativeClass` types.
+
+use std::any::TypeId;
+
+use crate::core_types::Variant;
+use crate::export::user_data::{Map, UserData};
+use crate::export::{class_registry, ClassBuilder, NativeClass, NativeClassMethods};
+use crate::object::ownership::Ownership;
+use crate::object::{SubClass, TInstance, TRef};
+
+/// Trait for `NativeClass`es that extend another Rust `NativeClass`, the super class.
+///
+/// The super class data is stored in a [`Super`] field of the sub class. Once the super class
+/// is registered with [`ClassBuilder::super_class`](crate::export::ClassBuilder::super_class),
+/// the sub class exposes all properties, methods and signals registered by the super class,
+/// which operate on that field. `Ref::cast_instance` and `Instance::from_base` also succeed for
+/// the super class when the object has the sub class attached.
+///
+/// The `NativeClass` derive macro implements this trait and registers the super class for the
+/// field marked with `#[super_class]`.
+///
+/// # Examples
+///
+/// ```
+/// use gdnative::prelude::*;
+/// use gdnative::export::Super;
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node2D)]
+/// #[no_constructor]
+/// struct Character {
+///     #[property]
+///     health: i64,
+/// }
+///
+/// #[methods]
+/// impl Character {
+///     #[method]
+///     fn take_damage(&mut self, amount: i64) {
+///         self.health -= amount;
+///     }
+/// }
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node2D)]
+/// #[no_constructor]
+/// struct Enemy {
+///     #[super_class]
+///     character: Super<Character>,
+///     #[property]
+///     bounty: i64,
+/// }
+///
+/// #[methods]
+/// impl Enemy {
+///     // Also exported are the `health` property and the `take_damage` method of `Character`
+///     #[method]
+///     fn hit(&self, #[base] owner: TRef<Node2D>) {
+///         let character = self.character.instance(owner);
+///         character
+///             .map_mut(|character, _| character.take_damage(10))
+///             .expect("super class data should not be borrowed");
+///     }
+/// }
+/// ```
+pub trait NativeSubClass: NativeClass {
+    /// The Rust class extended by this class.
+    type Super: NativeClass;
+
+    /// Returns the super class data of this instance.
+    fn super_data(&self) -> &Super<Self::Super>;
+}
+
+/// Storage for the super class data of a [`NativeSubClass`].
+///
+/// The data is kept in the user data wrapper of the super class, so it can be accessed
+/// independently from the sub class, as an instance of the super class with the same owner.
+#[derive(Debug)]
+pub struct Super<T: NativeClass> {
+    data: T::UserData,
+}
+
+impl<T: NativeClass> Super<T> {
+    /// Wraps the super class data `value`.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Super {
+            data: T::UserData::new(value),
+        }
+    }
+
+    /// Returns a reference to the user data wrapper of the super class data.
+    #[inline]
+    pub fn user_data(&self) -> &T::UserData {
+        &self.data
+    }
+
+    /// Returns the super class data as an instance of the super class owned by `owner`,
+    /// which should be the owner of the sub class instance. This can be used to call "super"
+    /// methods that take `TInstance` or the owner.
+    #[inline]
+    pub fn instance<'a, B, Own>(&self, owner: TRef<'a, B, Own>) -> TInstance<'a, T, Own>
+    where
+        B: SubClass<T::Base>,
+        Own: Ownership,
+    {
+        TInstance::from_parts(owner.upcast(), self.data.clone())
+    }
+}
+
+impl<T: NativeClass + Default> Default for Super<T> {
+    #[inline]
+    fn default() -> Self {
+        Super::new(T::default())
+    }
+}
+
+/// A step converting the user data of a `NativeSubClass` to the user data of its super class.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Upcast {
+    /// Takes a borrowed user data pointer of the sub class and returns a new owned user data
+    /// pointer of the super class, or `None` if the sub class data could not be accessed.
+    apply: unsafe fn(*const libc::c_void) -> Option<*const libc::c_void>,
+    /// Drops an owned user data pointer of the super class.
+    release: unsafe fn(*const libc::c_void),
+}
+
+impl Upcast {
+    fn of<C>() -> Self
+    where
+        C: NativeSubClass,
+        C::UserData: Map,
+    {
+        unsafe fn apply<C>(user_data: *const libc::c_void) -> Option<*const libc::c_void>
+        where
+            C: NativeSubClass,
+            C::UserData: Map,
+        {
+            let data = C::UserData::clone_from_user_data_unchecked(user_data);
+            match data.map(|script| script.super_data().data.clone()) {
+                Ok(super_data) => Some(super_data.into_user_data()),
+                Err(err) => {
+                    godot_error!(
+                        "gdnative-core: super class data of {} could not be accessed: {:?}",
+                        class_registry::class_name_or_default::<C>(),
+                        err,
+                    );
+                    None
+                }
+            }
+        }
+
+        unsafe fn release<T: NativeClass>(user_data: *const libc::c_void) {
+            drop(T::UserData::consume_user_data_unchecked(user_data));
+        }
+
+        Upcast {
+            apply: apply::<C>,
+            release: release::<C::Super>,
+        }
+    }
+}
+
+/// Registers the members of the super class of `C` on the class being registered by
+/// `class_builder`.
+#[allow(deprecated)] // Remove once register_properties() and register() have been renamed
+pub(super) fn register<C>(class_builder: &ClassBuilder<C>)
+where
+    C: NativeSubClass,
+    C::UserData: Map,
+    C::Super: NativeClassMethods,
+    C::Base: SubClass<<C::Super as NativeClass>::Base>,
+{
+    let mut upcasts = class_builder.upcasts.clone();
+    upcasts.push(Upcast::of::<C>());
+
+    class_registry::register_super_class(
+        class_builder.class_type,
+        TypeId::of::<C::Super>(),
+        upcasts.clone(),
+    );
+
+    let builder = class_builder.for_super_class::<C::Super>(upcasts);
+    C::Super::register_properties(&builder);
+    C::Super::register(&builder);
+}
+
+/// Calls `f` with the user data pointer obtained by applying `upcasts` to `user_data`, then
+/// releases it. Returns `None` without calling `f` if any of the upcasts failed.
+unsafe fn with_upcast<R>(
+    upcasts: &[Upcast],
+    user_data: *mut libc::c_void,
+    f: impl FnOnce(*mut libc::c_void) -> R,
+) -> Option<R> {
+    let mut current = user_data as *const libc::c_void;
+    let mut produced_by: Option<&Upcast> = None;
+
+    for upcast in upcasts {
+        let next = (upcast.apply)(current);
+        if let Some(previous) = produced_by {
+            (previous.release)(current);
+        }
+        current = next?;
+        produced_by = Some(upcast);
+    }
+
+    let result = f(current as *mut libc::c_void);
+
+    if let Some(last) = produced_by {
+        (last.release)(current);
+    }
+
+    Some(result)
+}
+
+/// Returns a new user data wrapper of `T` for the user data of a sub class, given the
+/// `upcasts` from the sub class to `T`.
+#[inline]
+pub(crate) unsafe fn upcast_user_data<T: NativeClass>(
+    upcasts: &[Upcast],
+    user_data: *mut libc::c_void,
+) -> Option<T::UserData> {
+    with_upcast(upcasts, user_data, |user_data| {
+        T::UserData::clone_from_user_data_unchecked(user_data)
+    })
+}
+
+/// Method data of wrapped functions, pairing the original function with the upcasts to apply
+/// to the user data before calling it.
+struct Upcasted<F> {
+    inner: F,
+    upcasts: Vec<Upcast>,
+}
+
+/// Wraps a method registered by a super class, so it is called with the super class data.
+pub(super) fn upcast_method(
+    upcasts: &[Upcast],
+    method: sys::godot_instance_method,
+) -> sys::godot_instance_method {
+    unsafe extern "C" fn invoke(
+        this: *mut sys::godot_object,
+        method_data: *mut libc::c_void,
+        user_data: *mut libc::c_void,
+        num_args: libc::c_int,
+        args: *mut *mut sys::godot_variant,
+    ) -> sys::godot_variant {
+        let data = &*(method_data as *const Upcasted<sys::godot_instance_method>);
+        let method = data.inner.method.expect("wrapped method should be set");
+
+        if user_data.is_null() {
+            // Let the wrapped method report it
+            return method(this, data.inner.method_data, user_data, num_args, args);
+        }
+
+        with_upcast(&data.upcasts, user_data, |user_data| {
+            method(this, data.inner.method_data, user_data, num_args, args)
+        })
+        .unwrap_or_else(|| Variant::nil().leak())
+    }
+
+    unsafe extern "C" fn free_func(method_data: *mut libc::c_void) {
+        let data = Box::from_raw(method_data as *mut Upcasted<sys::godot_instance_method>);
+        if let Some(free_func) = data.inner.free_func {
+            free_func(data.inner.method_data);
+        }
+    }
+
+    if method.method.is_none() {
+        return method;
+    }
+
+    let data = Box::new(Upcasted {
+        inner: method,
+        upcasts: upcasts.to_vec(),
+    });
+
+    sys::godot_instance_method {
+        method: Some(invoke),
+        method_data: Box::into_raw(data) as *mut _,
+        free_func: Some(free_func),
+    }
+}
+
+/// Wraps a property setter registered by a super class, so it is called with the super class
+/// data.
+pub(super) fn upcast_setter(
+    upcasts: &[Upcast],
+    set: sys::godot_property_set_func,
+) -> sys::godot_property_set_func {
+    unsafe extern "C" fn invoke(
+        this: *mut sys::godot_object,
+        method_data: *mut libc::c_void,
+        user_data: *mut libc::c_void,
+        val: *mut sys::godot_variant,
+    ) {
+        let data = &*(method_data as *const Upcasted<sys::godot_property_set_func>);
+        let set_func = data.inner.set_func.expect("wrapped setter should be set");
+
+        if user_data.is_null() {
+            set_func(this, data.inner.method_data, user_data, val);
+            return;
+        }
+
+        with_upcast(&data.upcasts, user_data, |user_data| {
+            set_func(this, data.inner.method_data, user_data, val)
+        });
+    }
+
+    unsafe extern "C" fn free_func(method_data: *mut libc::c_void) {
+        let data = Box::from_raw(method_data as *mut Upcasted<sys::godot_property_set_func>);
+        if let Some(free_func) = data.inner.free_func {
+            free_func(data.inner.method_data);
+        }
+    }
+
+    if set.set_func.is_none() {
+        return set;
+    }
+
+    let data = Box::new(Upcasted {
+        inner: set,
+        upcasts: upcasts.to_vec(),
+    });
+
+    sys::godot_property_set_func {
+        set_func: Some(invoke),
+        method_data: Box::into_raw(data) as *mut _,
+        free_func: Some(free_func),
+    }
+}
+
+/// Wraps a property getter registered by a super class, so it is called with the super class
+/// data.
+pub(super) fn upcast_getter(
+    upcasts: &[Upcast],
+    get: sys::godot_property_get_func,
+) -> sys::godot_property_get_func {
+    unsafe extern "C" fn invoke(
+        this: *mut sys::godot_object,
+        method_data: *mut libc::c_void,
+        user_data: *mut libc::c_void,
+    ) -> sys::godot_variant {
+        let data = &*(method_data as *const Upcasted<sys::godot_property_get_func>);
+        let get_func = data.inner.get_func.expect("wrapped getter should be set");
+
+        if user_data.is_null() {
+            return get_func(this, data.inner.method_data, user_data);
+        }
+
+        with_upcast(&data.upcasts, user_data, |user_data| {
+            get_func(this, data.inner.method_data, user_data)
+        })
+        .unwrap_or_else(|| Variant::nil().leak())
+    }
+
+    unsafe extern "C" fn free_func(method_data: *mut libc::c_void) {
+        let data = Box::from_raw(method_data as *mut Upcasted<sys::godot_property_get_func>);
+        if let Some(free_func) = data.inner.free_func {
+            free_func(data.inner.method_data);
+        }
+    }
+
+    if get.get_func.is_none() {
+        return get;
+    }
+
+    let data = Box::new(Upcasted {
+        inner: get,
+        upcasts: upcasts.to_vec(),
+    });
+
+    sys::godot_property_get_func {
+        get_func: Some(invoke),
+        method_data: Box::into_raw(data) as *mut _,
+        free_func: Some(free_func),
+    }
+}
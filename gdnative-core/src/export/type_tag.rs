@@ -65,16 +65,26 @@ pub(crate) unsafe fn check<T>(tag: *const libc::c_void) -> bool
 where
     T: NativeClass,
 {
+    Tag::of::<T>() == get(tag)
+}
+
+/// Returns the type ID of the type that `tag` corresponds to. `tag` must be one returned by
+/// `create`.
+#[inline]
+pub(crate) unsafe fn type_id(tag: *const libc::c_void) -> TypeId {
+    get(tag).type_id
+}
+
+unsafe fn get(tag: *const libc::c_void) -> Tag {
     if USE_TRANSMUTE {
         // Safety: USE_TRANSMUTE is only true if layouts match
-        Tag::of::<T>() == *(&tag as *const *const libc::c_void as *const Tag)
+        *(&tag as *const *const libc::c_void as *const Tag)
     } else {
         let tags = TAGS.as_ref().expect("tag should be created by `create`");
         let idx = tag as usize;
-        let tag = tags
+        *tags
             .get_index(idx & MAGIC_MASK)
-            .expect("tag should be created by `create`");
-        Tag::of::<T>() == *tag
+            .expect("tag should be created by `create`")
     }
 }
 
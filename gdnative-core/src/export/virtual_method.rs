@@ -0,0 +1,69 @@
+//! Compile-time checks for overrides of engine virtual methods.
+
+use crate::core_types::*;
+use crate::object::ownership::Shared;
+use crate::object::{GodotObject, Ref, SubClass};
+
+/// Trait for types that can be used in place of the engine type `T`, as an argument or return
+/// type of an override of an engine virtual method.
+///
+/// This is used by the `*Virtual` traits in the generated API bindings, which are checked by
+/// `#[method(virtual)]`. Apart from `T` itself, the following types are accepted:
+///
+/// - Any integer type for `int`, and `f32` for `float`.
+/// - `String` for `String`.
+/// - `Ref<U>` or `Option<Ref<U>>` for objects of a class `T`, where `U` is `T` or a subclass.
+/// - Any type that implements both `ToVariant` and `FromVariant` for `Variant`.
+///
+/// Note that virtual methods with enum arguments or return values, including `Error`, use `i64`
+/// as the engine type.
+pub trait VirtualType<T> {}
+
+macro_rules! impl_virtual_type {
+    ($engine_ty:ty: $($ty:ty),*) => {
+        $(impl VirtualType<$engine_ty> for $ty {})*
+    };
+}
+
+impl_virtual_type!((): ());
+impl_virtual_type!(bool: bool);
+impl_virtual_type!(i64: i8, i16, i32, i64, u8, u16, u32, u64);
+impl_virtual_type!(f64: f32, f64);
+impl_virtual_type!(GodotString: GodotString, String);
+impl_virtual_type!(Vector2: Vector2);
+impl_virtual_type!(Vector3: Vector3);
+impl_virtual_type!(Quat: Quat);
+impl_virtual_type!(Transform: Transform);
+impl_virtual_type!(Transform2D: Transform2D);
+impl_virtual_type!(Rect2: Rect2);
+impl_virtual_type!(Plane: Plane);
+impl_virtual_type!(Basis: Basis);
+impl_virtual_type!(Color: Color);
+impl_virtual_type!(NodePath: NodePath);
+impl_virtual_type!(Aabb: Aabb);
+impl_virtual_type!(Rid: Rid);
+impl_virtual_type!(VariantArray: VariantArray);
+impl_virtual_type!(Dictionary: Dictionary);
+impl_virtual_type!(ByteArray: ByteArray);
+impl_virtual_type!(StringArray: StringArray);
+impl_virtual_type!(Vector2Array: Vector2Array);
+impl_virtual_type!(Vector3Array: Vector3Array);
+impl_virtual_type!(ColorArray: ColorArray);
+impl_virtual_type!(Int32Array: Int32Array);
+impl_virtual_type!(Float32Array: Float32Array);
+
+impl<T> VirtualType<Variant> for T where T: ToVariant + FromVariant {}
+
+impl<T, U> VirtualType<Option<Ref<T, Shared>>> for Ref<U, Shared>
+where
+    T: GodotObject,
+    U: GodotObject + SubClass<T>,
+{
+}
+
+impl<T, U> VirtualType<Option<Ref<T, Shared>>> for Option<Ref<U, Shared>>
+where
+    T: GodotObject,
+    U: GodotObject + SubClass<T>,
+{
+}
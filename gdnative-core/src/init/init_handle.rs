@@ -4,6 +4,9 @@ use crate::export::{
 };
 use crate::object::{GodotObject, RawObject, TRef};
 use crate::private::get_api;
+
+use super::script_resources;
+use std::any::TypeId;
 use std::borrow::Cow;
 use std::ffi::CString;
 use std::ptr;
@@ -68,13 +71,55 @@ impl InitHandle {
         self.add_maybe_tool_class_as::<C>(Cow::Owned(name), true)
     }
 
+    /// Registers all classes that opted into automatic registration with the
+    /// `#[auto_register]` attribute of `#[derive(NativeClass)]`, in no particular order.
+    ///
+    /// Classes from all crates linked into the library are included. Classes that are also
+    /// added manually result in a panic, like any other class that is registered twice. Classes
+    /// with the same name, e.g. from different modules, are reported the same way.
+    ///
+    /// The names of all these classes are known before the first one is registered, so
+    /// exported `Instance` properties of these classes are restricted to their script classes
+    /// regardless of the order.
+    ///
+    /// Requires the `inventory` feature.
+    #[cfg(feature = "inventory")]
+    #[inline]
+    pub fn add_all_classes(self) {
+        for plugin in crate::private::inventory::iter::<crate::private::AutoInitPlugin> {
+            (plugin.declare)();
+        }
+        for plugin in crate::private::inventory::iter::<crate::private::AutoInitPlugin> {
+            (plugin.f)(self);
+        }
+    }
+
     #[inline]
     fn add_maybe_tool_class_as<C>(self, name: Cow<'static, str>, is_tool: bool)
     where
         C: NativeClassMethods,
     {
+        if script_resources::try_collect(
+            TypeId::of::<C>(),
+            std::any::type_name::<C>(),
+            &name,
+            C::Base::class_name(),
+            is_tool,
+        ) {
+            return;
+        }
+
         let c_class_name = CString::new(&*name).unwrap();
 
+        if let Some(other_type_name) = class_registry::find_name_conflict::<C>(&name) {
+            panic!(
+                "`{type_name}` cannot be registered as `{name}`, because the name is already used by `{other_type_name}`",
+                type_name = std::any::type_name::<C>(),
+                name = name,
+                other_type_name = other_type_name,
+            );
+        }
+
         if let Some(class_info) = class_registry::register_class_as::<C>(name) {
             panic!(
                 "`{type_name}` has already been registered as `{old_name}`",
@@ -118,8 +163,11 @@ impl InitHandle {
                     };
 
                     let val = match panic::catch_unwind(AssertUnwindSafe(|| {
-                        emplace::take()
-                            .unwrap_or_else(|| C::init(TRef::new(C::Base::cast_ref(owner))))
+                        let owner = TRef::new(C::Base::cast_ref(owner));
+                        match emplace::take::<C>() {
+                            Some(constructor) => constructor(owner),
+                            None => C::init(owner),
+                        }
                     })) {
                         Ok(val) => val,
                         Err(_) => {
@@ -39,9 +39,11 @@
 mod info;
 mod init_handle;
 mod macros;
+mod script_resources;
 
 pub use info::*;
 pub use init_handle::*;
+pub use script_resources::*;
 
 pub use crate::{
     godot_gdnative_init, godot_gdnative_terminate, godot_init, godot_nativescript_init,
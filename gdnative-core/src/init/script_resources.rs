@@ -0,0 +1,668 @@
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use super::InitHandle;
+
+thread_local! {
+    static COLLECTOR: RefCell<Option<Vec<CollectedClass>>> = RefCell::new(None);
+}
+
+struct CollectedClass {
+    type_id: TypeId,
+    type_name: &'static str,
+    class: RegisteredClass,
+}
+
+/// A class added through [`InitHandle`], as recorded by [`InitHandle::collect_classes`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegisteredClass {
+    /// The name the class is registered as.
+    pub name: String,
+    /// The name of the Godot base class.
+    pub base: &'static str,
+    /// Whether the class is registered as a tool class.
+    pub is_tool: bool,
+}
+
+impl InitHandle {
+    /// Runs the init callback `init` without the engine, and returns the classes it adds,
+    /// in order. The classes are not actually registered. This can be used to generate or check
+    /// the script resources of a Godot project with [`ScriptResources`], e.g. in a test.
+    ///
+    /// Names are checked for duplicates, in the same way as during normal registration.
+    ///
+    /// # Panics
+    ///
+    /// If `init` adds a type twice, or adds two types with the same name.
+    #[inline]
+    pub fn collect_classes<F: FnOnce(InitHandle)>(init: F) -> Vec<RegisteredClass> {
+        COLLECTOR.with(|collector| {
+            assert!(
+                collector.borrow_mut().replace(Vec::new()).is_none(),
+                "collect_classes cannot be nested"
+            );
+        });
+
+        let handle = unsafe { InitHandle::new(std::ptr::null_mut()) };
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| init(handle)));
+        let classes = COLLECTOR.with(|collector| collector.borrow_mut().take().unwrap_or_default());
+
+        if let Err(payload) = result {
+            std::panic::resume_unwind(payload);
+        }
+
+        classes
+            .into_iter()
+            .map(|collected| collected.class)
+            .collect()
+    }
+}
+
+/// Records a class if `collect_classes` is running on this thread. Returns `false` otherwise,
+/// in which case the class should be registered with the engine.
+pub(super) fn try_collect(
+    type_id: TypeId,
+    type_name: &'static str,
+    name: &str,
+    base: &'static str,
+    is_tool: bool,
+) -> bool {
+    COLLECTOR.with(|collector| {
+        let mut collector = collector.borrow_mut();
+        let classes = match collector.as_mut() {
+            Some(classes) => classes,
+            None => return false,
+        };
+
+        for other in classes.iter() {
+            if other.type_id == type_id {
+                panic!(
+                    "`{type_name}` has already been registered as `{old_name}`",
+                    type_name = type_name,
+                    old_name = other.class.name,
+                );
+            }
+
+            if other.class.name == name {
+                panic!(
+                    "`{type_name}` cannot be registered as `{name}`, because the name is already used by `{other_type_name}`",
+                    type_name = type_name,
+                    name = name,
+                    other_type_name = other.type_name,
+                );
+            }
+        }
+
+        classes.push(CollectedClass {
+            type_id,
+            type_name,
+            class: RegisteredClass {
+                name: name.to_string(),
+                base,
+                is_tool,
+            },
+        });
+        true
+    })
+}
+
+/// Generator for the `.gdnlib` and `.gdns` resource files that make the classes of a
+/// GDNative library usable in a Godot project.
+///
+/// Paths starting with `res://` are relative to the root directory of the project.
+///
+/// # Examples
+///
+/// Checking a project for outdated script resources in a test:
+///
+/// ```no_run
+/// use gdnative::prelude::*;
+/// use gdnative::init::ScriptResources;
+///
+/// fn init(handle: InitHandle) {
+///     // handle.add_class::<...>();
+/// }
+///
+/// let classes = InitHandle::collect_classes(init);
+/// let resources = ScriptResources::new("res://my_library.gdnlib", "res://scripts", classes)
+///     .with_default_entries("res://bin", "my_library");
+///
+/// let issues = resources.check("../godot").unwrap();
+/// assert!(issues.is_empty(), "run the generator to fix: {:?}", issues);
+///
+/// // To (re-)generate the resources instead:
+/// resources.write("../godot").unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct ScriptResources {
+    library_path: String,
+    script_dir: String,
+    classes: Vec<RegisteredClass>,
+    entries: Vec<(String, String)>,
+    symbol_prefix: String,
+    reloadable: bool,
+    script_classes: Vec<String>,
+}
+
+impl ScriptResources {
+    /// Creates a generator for the library resource at `library_path`, with one script
+    /// resource per class in `script_dir`.
+    #[inline]
+    pub fn new(
+        library_path: impl Into<String>,
+        script_dir: impl Into<String>,
+        classes: Vec<RegisteredClass>,
+    ) -> Self {
+        ScriptResources {
+            library_path: library_path.into(),
+            script_dir: script_dir.into(),
+            classes,
+            entries: Vec::new(),
+            symbol_prefix: "godot_".to_string(),
+            reloadable: false,
+            script_classes: Vec::new(),
+        }
+    }
+
+    /// Adds the path of the dynamic library for a platform, e.g. `X11.64` or `Android.arm64-v8a`.
+    #[inline]
+    pub fn with_entry(mut self, platform: impl Into<String>, path: impl Into<String>) -> Self {
+        self.entries.push((platform.into(), path.into()));
+        self
+    }
+
+    /// Adds entries for 64-bit Linux, Windows and macOS, for a crate named `crate_name` whose
+    /// libraries are copied to `dir`, using the file names produced by Cargo.
+    #[inline]
+    pub fn with_default_entries(self, dir: &str, crate_name: &str) -> Self {
+        let name = crate_name.replace('-', "_");
+        self.with_entry("X11.64", join_path(dir, &format!("lib{}.so", name)))
+            .with_entry("Server.64", join_path(dir, &format!("lib{}.so", name)))
+            .with_entry("Windows.64", join_path(dir, &format!("{}.dll", name)))
+            .with_entry("OSX.64", join_path(dir, &format!("lib{}.dylib", name)))
+    }
+
+    /// Sets the prefix of the GDNative entry points, as set with the `godot_init!` family of
+    /// macros. Defaults to `godot_`.
+    #[inline]
+    pub fn with_symbol_prefix(mut self, symbol_prefix: impl Into<String>) -> Self {
+        self.symbol_prefix = symbol_prefix.into();
+        self
+    }
+
+    /// Sets whether the library may be reloaded when the editor regains focus. Defaults to
+    /// `false`.
+    #[inline]
+    pub fn with_reloadable(mut self, reloadable: bool) -> Self {
+        self.reloadable = reloadable;
+        self
+    }
+
+    /// Declares the script of the class `name` as a global script class, so the editor can
+    /// restrict exported `Instance` properties of that class to resources using the script.
+    ///
+    /// Global script classes share a namespace with GDScript `class_name`s and are listed in
+    /// the editor, so this should only be used for classes that are exported as `Instance`
+    /// properties.
+    #[inline]
+    pub fn with_script_class(mut self, name: impl Into<String>) -> Self {
+        self.script_classes.push(name.into());
+        self
+    }
+
+    /// Returns the path of the script resource for `class`.
+    #[inline]
+    pub fn script_path(&self, class: &RegisteredClass) -> String {
+        join_path(&self.script_dir, &format!("{}.gdns", class.name))
+    }
+
+    /// Returns the contents of the `.gdnlib` library resource.
+    #[inline]
+    pub fn gdnlib(&self) -> String {
+        let mut out = String::new();
+        out.push_str("[general]\n\n");
+        out.push_str("singleton=false\n");
+        out.push_str("load_once=true\n");
+        let _ = writeln!(out, "symbol_prefix=\"{}\"", self.symbol_prefix);
+        let _ = writeln!(out, "reloadable={}", self.reloadable);
+
+        out.push_str("\n[entry]\n\n");
+        for (platform, path) in &self.entries {
+            let _ = writeln!(out, "{}=\"{}\"", platform, path);
+        }
+
+        out.push_str("\n[dependencies]\n\n");
+        for (platform, _) in &self.entries {
+            let _ = writeln!(out, "{}=[  ]", platform);
+        }
+
+        out
+    }
+
+    /// Returns the contents of the `.gdns` script resource for `class`.
+    ///
+    /// The script is also declared as a global script class with the name of the class, if it
+    /// is added with [`with_script_class`](Self::with_script_class).
+    #[inline]
+    pub fn gdns(&self, class: &RegisteredClass) -> String {
+        let mut out = format!(
+            "[gd_resource type=\"NativeScript\" load_steps=2 format=2]\n\
+             \n\
+             [ext_resource path=\"{library}\" type=\"GDNativeLibrary\" id=1]\n\
+             \n\
+             [resource]\n\
+             resource_name = \"{name}\"\n\
+             class_name = \"{name}\"\n\
+             library = ExtResource( 1 )\n",
+            library = self.library_path,
+            name = class.name,
+        );
+        if self.script_classes.contains(&class.name) {
+            let _ = writeln!(out, "script_class_name = \"{}\"", class.name);
+        }
+        out
+    }
+
+    /// Writes the library resource and all script resources to the project in `project_dir`,
+    /// replacing existing files.
+    #[inline]
+    pub fn write(&self, project_dir: impl AsRef<Path>) -> io::Result<()> {
+        let project_dir = project_dir.as_ref();
+
+        write_file(
+            &project_path(project_dir, &self.library_path),
+            &self.gdnlib(),
+        )?;
+        for class in &self.classes {
+            let path = project_path(project_dir, &self.script_path(class));
+            write_file(&path, &self.gdns(class))?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks the project in `project_dir` for script resources that are out of date with the
+    /// registered classes. Returns an empty list if everything is up to date.
+    ///
+    /// The library resource is compared by its sections and keys, so the order of keys and
+    /// formatting changes made by the editor when saving it are not reported.
+    ///
+    /// Script resources are found anywhere in the project, not only in the script directory,
+    /// as long as they refer to the same library resource.
+    #[inline]
+    pub fn check(&self, project_dir: impl AsRef<Path>) -> io::Result<Vec<ResourceIssue>> {
+        let project_dir = project_dir.as_ref();
+        let mut issues = Vec::new();
+
+        let library_path = project_path(project_dir, &self.library_path);
+        match fs::read_to_string(&library_path) {
+            Ok(contents) => {
+                if parse_resource(&contents) != parse_resource(&self.gdnlib()) {
+                    issues.push(ResourceIssue::StaleLibrary { path: library_path });
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                issues.push(ResourceIssue::MissingLibrary { path: library_path });
+            }
+            Err(err) => return Err(err),
+        }
+
+        let mut scripts = Vec::new();
+        find_scripts(project_dir, &mut scripts)?;
+
+        let mut found = Vec::new();
+        for path in scripts {
+            let contents = fs::read_to_string(&path)?;
+            let uses_library = contents
+                .lines()
+                .filter(|line| line.starts_with("[ext_resource"))
+                .any(|line| quoted_value(line, "path") == Some(self.library_path.as_str()));
+            if !uses_library {
+                continue;
+            }
+
+            let class_name = contents
+                .lines()
+                .find(|line| line.starts_with("class_name"))
+                .and_then(|line| quoted_value(line, "class_name"));
+
+            match class_name {
+                Some(class_name) if self.classes.iter().any(|c| c.name == class_name) => {
+                    found.push(class_name.to_string());
+                }
+                class_name => issues.push(ResourceIssue::UnknownClass {
+                    path,
+                    class_name: class_name.unwrap_or_default().to_string(),
+                }),
+            }
+        }
+
+        for class in &self.classes {
+            if !found.contains(&class.name) {
+                issues.push(ResourceIssue::MissingScript {
+                    path: project_path(project_dir, &self.script_path(class)),
+                    class_name: class.name.clone(),
+                });
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// A problem with the script resources of a project, found by [`ScriptResources::check`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResourceIssue {
+    /// The library resource does not exist.
+    MissingLibrary { path: PathBuf },
+    /// The library resource has different contents than would be generated.
+    StaleLibrary { path: PathBuf },
+    /// No script resource refers to the registered class `class_name`.
+    MissingScript { path: PathBuf, class_name: String },
+    /// A script resource refers to a class that is not registered by the library.
+    UnknownClass { path: PathBuf, class_name: String },
+}
+
+fn join_path(dir: &str, file: &str) -> String {
+    if dir.ends_with('/') {
+        format!("{}{}", dir, file)
+    } else {
+        format!("{}/{}", dir, file)
+    }
+}
+
+fn project_path(project_dir: &Path, res_path: &str) -> PathBuf {
+    project_dir.join(res_path.trim_start_matches("res://"))
+}
+
+fn write_file(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+/// Section and key-value pairs of a resource file in Godot's config file format.
+type ResourceSections = BTreeMap<String, BTreeMap<String, String>>;
+
+/// Parses the sections and keys of a resource file, ignoring their order, comments and the
+/// whitespace that the editor may change when saving.
+fn parse_resource(contents: &str) -> ResourceSections {
+    let mut sections = ResourceSections::new();
+    let mut section = String::new();
+
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_string();
+            sections.entry(section.clone()).or_default();
+        } else if let Some((key, value)) = line.split_once('=') {
+            sections
+                .entry(section.clone())
+                .or_default()
+                .insert(key.trim().to_string(), strip_unquoted_whitespace(value));
+        }
+    }
+
+    sections
+}
+
+/// Removes whitespace outside of string literals, e.g. in `[  ]` or `ExtResource( 1 )`.
+fn strip_unquoted_whitespace(value: &str) -> String {
+    let mut stripped = String::with_capacity(value.len());
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in value.chars() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c.is_whitespace() {
+            continue;
+        }
+        stripped.push(c);
+    }
+
+    stripped
+}
+
+fn find_scripts(dir: &Path, out: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map_or(false, |name| name.starts_with('.'));
+
+        if is_hidden {
+            continue;
+        } else if path.is_dir() {
+            find_scripts(&path, out)?;
+        } else if path.extension().map_or(false, |ext| ext == "gdns") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the quoted value of `key` in a line like `key = "value"` or `[tag key="value"]`.
+fn quoted_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let start = line.find(key)? + key.len();
+    let rest = line[start..].trim_start().strip_prefix('=')?.trim_start();
+    let rest = rest.strip_prefix('"')?;
+    let end = rest.find('"')?;
+    Some(&rest[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class(name: &str) -> RegisteredClass {
+        RegisteredClass {
+            name: name.to_string(),
+            base: "Node",
+            is_tool: false,
+        }
+    }
+
+    #[test]
+    fn gdns_contents() {
+        let resources = ScriptResources::new("res://lib.gdnlib", "res://scripts", vec![]);
+        let gdns = resources.gdns(&class("Player"));
+
+        assert!(gdns
+            .contains("[ext_resource path=\"res://lib.gdnlib\" type=\"GDNativeLibrary\" id=1]\n"));
+        assert!(gdns.contains("\nclass_name = \"Player\"\n"));
+        assert!(!gdns.contains("script_class_name"));
+        assert_eq!(
+            Some("Player"),
+            quoted_value("class_name = \"Player\"", "class_name")
+        );
+        assert_eq!(
+            "res://scripts/Player.gdns",
+            resources.script_path(&class("Player"))
+        );
+
+        let resources = resources.with_script_class("Player");
+        assert!(resources
+            .gdns(&class("Player"))
+            .ends_with("\nscript_class_name = \"Player\"\n"));
+        assert!(!resources
+            .gdns(&class("Other"))
+            .contains("script_class_name"));
+    }
+
+    /// Returns an empty directory for a test project, unique to the test and process.
+    fn temp_project(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "gdnative-script-resources-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn resources() -> ScriptResources {
+        ScriptResources::new(
+            "res://lib.gdnlib",
+            "res://scripts",
+            vec![class("Player"), class("Enemy")],
+        )
+        .with_default_entries("res://bin", "my-game")
+    }
+
+    #[test]
+    fn check_written_resources() {
+        let project = temp_project("written");
+        let resources = resources();
+
+        assert_eq!(
+            vec![
+                ResourceIssue::MissingLibrary {
+                    path: project.join("lib.gdnlib")
+                },
+                ResourceIssue::MissingScript {
+                    path: project.join("scripts/Player.gdns"),
+                    class_name: "Player".to_string(),
+                },
+                ResourceIssue::MissingScript {
+                    path: project.join("scripts/Enemy.gdns"),
+                    class_name: "Enemy".to_string(),
+                },
+            ],
+            resources.check(&project).unwrap()
+        );
+
+        resources.write(&project).unwrap();
+        assert_eq!(
+            resources.gdns(&class("Player")),
+            fs::read_to_string(project.join("scripts/Player.gdns")).unwrap()
+        );
+        assert!(resources.check(&project).unwrap().is_empty());
+
+        fs::remove_dir_all(&project).unwrap();
+    }
+
+    #[test]
+    fn check_resaved_library() {
+        let project = temp_project("resaved");
+        let resources = resources();
+        resources.write(&project).unwrap();
+
+        // Same contents with keys reordered and formatted differently
+        let resaved = "[general]\n\
+                       load_once=true\n\
+                       singleton=false\n\
+                       reloadable=false\n\
+                       symbol_prefix = \"godot_\"\n\
+                       \n\
+                       [dependencies]\n\
+                       \n\
+                       OSX.64=[ ]\n\
+                       Windows.64=[ ]\n\
+                       Server.64=[  ]\n\
+                       X11.64=[]\n\
+                       \n\
+                       [entry]\n\
+                       \n\
+                       Windows.64=\"res://bin/my_game.dll\"\n\
+                       OSX.64=\"res://bin/libmy_game.dylib\"\n\
+                       X11.64=\"res://bin/libmy_game.so\"\n\
+                       Server.64=\"res://bin/libmy_game.so\"\n";
+        fs::write(project.join("lib.gdnlib"), resaved).unwrap();
+        assert!(resources.check(&project).unwrap().is_empty());
+
+        let changed = resaved.replace("my_game.dll", "other_game.dll");
+        fs::write(project.join("lib.gdnlib"), changed).unwrap();
+        assert_eq!(
+            vec![ResourceIssue::StaleLibrary {
+                path: project.join("lib.gdnlib")
+            }],
+            resources.check(&project).unwrap()
+        );
+
+        fs::remove_dir_all(&project).unwrap();
+    }
+
+    #[test]
+    fn check_moved_and_unknown_scripts() {
+        let project = temp_project("scripts");
+        let resources = resources();
+        resources.write(&project).unwrap();
+
+        // Scripts may be moved anywhere in the project
+        fs::create_dir_all(project.join("enemies")).unwrap();
+        fs::rename(
+            project.join("scripts/Enemy.gdns"),
+            project.join("enemies/Enemy.gdns"),
+        )
+        .unwrap();
+
+        // Scripts of other libraries are ignored
+        let other = ScriptResources::new("res://other.gdnlib", "res://scripts", vec![]);
+        fs::write(
+            project.join("scripts/Other.gdns"),
+            other.gdns(&class("Other")),
+        )
+        .unwrap();
+        assert!(resources.check(&project).unwrap().is_empty());
+
+        fs::write(
+            project.join("scripts/Removed.gdns"),
+            resources.gdns(&class("Removed")),
+        )
+        .unwrap();
+        assert_eq!(
+            vec![ResourceIssue::UnknownClass {
+                path: project.join("scripts/Removed.gdns"),
+                class_name: "Removed".to_string(),
+            }],
+            resources.check(&project).unwrap()
+        );
+
+        fs::remove_dir_all(&project).unwrap();
+    }
+
+    #[test]
+    fn parse_resource_sections() {
+        let sections =
+            parse_resource("; comment\n[general]\nname = \"a b\"\nlist=[ 1, 2 ]\n\n[empty]\n");
+
+        assert_eq!(2, sections.len());
+        assert_eq!("\"a b\"", sections["general"]["name"]);
+        assert_eq!("[1,2]", sections["general"]["list"]);
+        assert!(sections["empty"].is_empty());
+    }
+
+    #[test]
+    fn gdnlib_entries() {
+        let resources = ScriptResources::new("res://lib.gdnlib", "res://", vec![]);
+        assert_eq!("res://Player.gdns", resources.script_path(&class("Player")));
+
+        let gdnlib = resources
+            .with_default_entries("res://bin/", "my-game")
+            .gdnlib();
+
+        assert!(gdnlib.contains("X11.64=\"res://bin/libmy_game.so\"\n"));
+        assert!(gdnlib.contains("Windows.64=\"res://bin/my_game.dll\"\n"));
+        assert!(gdnlib.contains("OSX.64=[  ]\n"));
+        assert!(gdnlib.contains("symbol_prefix=\"godot_\"\n"));
+    }
+}
@@ -1,10 +1,11 @@
+use std::any::TypeId;
 use std::ptr::NonNull;
 
 use crate::core_types::{
     FromVariant, FromVariantError, GodotString, OwnedToVariant, ToVariant, Variant,
 };
 use crate::export::user_data::{Map, MapMut, MapOwned, UserData};
-use crate::export::{class_registry, emplace, NativeClass};
+use crate::export::{class_registry, emplace, super_class, NativeClass, NativeClassCtor};
 use crate::object::bounds::{
     AssumeSafeLifetime, LifetimeConstraint, RefImplBound, SafeAsRaw, SafeDeref,
 };
@@ -98,10 +99,32 @@ impl<T: NativeClass> Instance<T, Unique> {
     where
         T::Base: Instanciable,
     {
-        Self::maybe_emplace(Some(script))
+        Self::maybe_emplace(Some(Box::new(move |_owner| script)))
     }
 
-    fn maybe_emplace(script: Option<T>) -> Self
+    /// Creates a `T::Base` with the script `T` attached, constructed by the
+    /// [`NativeClassCtor<Args>`] implementation of `T` from `args`. `T::Base` must have a
+    /// zero-argument constructor.
+    ///
+    /// Unlike with [`Instance::emplace`], the constructor has access to the newly created
+    /// owner. See [`NativeClassCtor`] for an example.
+    ///
+    /// If `T::Base` is manually-managed, then the resulting `Instance` must be passed to
+    /// the engine or manually freed with `Instance::free`. Otherwise, the base object will be
+    /// leaked.
+    ///
+    /// Must be called after the library is initialized.
+    #[inline]
+    pub fn new_with<Args>(args: Args) -> Self
+    where
+        T: NativeClassCtor<Args>,
+        T::Base: Instanciable,
+        Args: 'static,
+    {
+        Self::maybe_emplace(Some(Box::new(move |owner| T::ctor(owner, args))))
+    }
+
+    fn maybe_emplace(constructor: Option<emplace::Constructor<T>>) -> Self
     where
         T::Base: Instanciable,
     {
@@ -165,8 +188,8 @@ impl<T: NativeClass> Instance<T, Unique> {
                 std::ptr::null_mut(),
             );
 
-            if let Some(script) = script {
-                emplace::place(script);
+            if let Some(constructor) = constructor {
+                emplace::place(constructor);
             }
 
             let mut args: [*const sys::godot_variant; 0] = [];
@@ -257,13 +280,11 @@ where
     /// Returns the original `Ref` if the cast failed.
     #[inline]
     pub fn try_from_base(owner: Ref<T::Base, Own>) -> Result<Self, Ref<T::Base, Own>> {
-        let user_data = match try_get_user_data_ptr::<T>(owner.as_raw()) {
-            Some(user_data) => user_data,
+        let script = match try_get_user_data::<T>(owner.as_raw()) {
+            Some(script) => script,
             None => return Err(owner),
         };
 
-        let script = unsafe { T::UserData::clone_from_user_data_unchecked(user_data) };
-
         Ok(Instance { owner, script })
     }
 
@@ -464,8 +485,14 @@ impl<'a, T: NativeClass, Own: Ownership> TInstance<'a, T, Own> {
     /// Try to downcast `TRef<'a, T::Base, Own>` to `TInstance<T>`.
     #[inline]
     pub fn try_from_base(owner: TRef<'a, T::Base, Own>) -> Option<Self> {
-        let user_data = try_get_user_data_ptr::<T>(owner.as_raw())?;
-        unsafe { Some(Self::from_raw_unchecked(owner, user_data)) }
+        let script = try_get_user_data::<T>(owner.as_raw())?;
+        Some(TInstance { owner, script })
+    }
+
+    /// Pairs an `owner` with a `script` wrapper. Internal interface.
+    #[inline]
+    pub(crate) fn from_parts(owner: TRef<'a, T::Base, Own>, script: T::UserData) -> Self {
+        TInstance { owner, script }
     }
 
     /// Pairs an `owner` and `user_data` without checking validity. Internal interface.
@@ -600,7 +627,9 @@ where
     }
 }
 
-fn try_get_user_data_ptr<T: NativeClass>(owner: &RawObject<T::Base>) -> Option<*mut libc::c_void> {
+/// Returns a new user data wrapper for the script of `owner`, if it is `T` or a
+/// `NativeSubClass` of `T`.
+fn try_get_user_data<T: NativeClass>(owner: &RawObject<T::Base>) -> Option<T::UserData> {
     unsafe {
         let api = get_api();
 
@@ -611,10 +640,21 @@ fn try_get_user_data_ptr<T: NativeClass>(owner: &RawObject<T::Base>) -> Option<*
             return None;
         }
 
-        if !crate::export::type_tag::check::<T>(type_tag) {
+        let user_data = (api.godot_nativescript_get_userdata)(owner_ptr);
+
+        if crate::export::type_tag::check::<T>(type_tag) {
+            return Some(T::UserData::clone_from_user_data_unchecked(user_data));
+        }
+
+        if user_data.is_null() {
             return None;
         }
 
-        Some((api.godot_nativescript_get_userdata)(owner_ptr))
+        let upcasts = class_registry::find_upcasts(
+            crate::export::type_tag::type_id(type_tag),
+            TypeId::of::<T>(),
+        )?;
+
+        super_class::upcast_user_data::<T>(&upcasts, user_data)
     }
 }
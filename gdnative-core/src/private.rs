@@ -2,6 +2,32 @@ use std::ffi::CString;
 
 use crate::sys;
 
+#[cfg(feature = "inventory")]
+pub use inventory;
+
+/// Registration function for a class that opted into automatic registration.
+#[cfg(feature = "inventory")]
+pub struct AutoInitPlugin {
+    pub f: fn(crate::init::InitHandle),
+    /// Declares the name of the class before any class is registered.
+    pub declare: fn(),
+}
+
+/// Declares the name of a class that is registered automatically.
+#[cfg(feature = "inventory")]
+#[inline]
+pub fn declare_auto_class<C>()
+where
+    C: crate::export::NativeClass + crate::export::StaticallyNamed,
+{
+    crate::export::class_registry::declare_class_name::<C>(std::borrow::Cow::Borrowed(
+        C::CLASS_NAME,
+    ));
+}
+
+#[cfg(feature = "inventory")]
+inventory::collect!(AutoInitPlugin);
+
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 // Unsafe helpers for sys
 
@@ -252,6 +278,7 @@ macro_rules! make_method_table {
 make_method_table!(struct ObjectMethodTable for Object {
     get_class,
     is_class,
+    emit_signal,
 });
 
 make_method_table!(struct ReferenceMethodTable for Reference {
@@ -0,0 +1,225 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::spanned::Spanned;
+use syn::{AttributeArgs, Fields, ItemEnum, Meta, NestedMeta};
+
+#[derive(Default)]
+struct ExportEnumAttrArgs {
+    prefix: Option<String>,
+}
+
+fn parse_args(args: AttributeArgs) -> Result<ExportEnumAttrArgs, syn::Error> {
+    let mut parsed = ExportEnumAttrArgs::default();
+
+    for arg in args {
+        let pair = match arg {
+            NestedMeta::Meta(Meta::NameValue(pair)) => pair,
+            _ => return Err(syn::Error::new(arg.span(), "expecting name-value pair")),
+        };
+
+        if !pair.path.is_ident("prefix") {
+            return Err(syn::Error::new(pair.span(), "unexpected argument"));
+        }
+
+        let prefix = if let syn::Lit::Str(lit_str) = &pair.lit {
+            lit_str.value()
+        } else {
+            return Err(syn::Error::new(
+                pair.lit.span(),
+                "prefix value is not a string literal",
+            ));
+        };
+
+        if parsed.prefix.replace(prefix).is_some() {
+            return Err(syn::Error::new(
+                pair.span(),
+                "`prefix` was set more than once",
+            ));
+        }
+    }
+
+    Ok(parsed)
+}
+
+pub(crate) fn derive_export_enum(
+    args: AttributeArgs,
+    item_enum: ItemEnum,
+) -> Result<TokenStream2, syn::Error> {
+    let args = parse_args(args)?;
+    let derived = crate::automatically_derived();
+
+    if !item_enum.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            item_enum.generics.span(),
+            "`#[export_enum]` does not support generic enums",
+        ));
+    }
+
+    if let Some(variant) = item_enum
+        .variants
+        .iter()
+        .find(|variant| !matches!(variant.fields, Fields::Unit))
+    {
+        return Err(syn::Error::new(
+            variant.fields.span(),
+            "`#[export_enum]` only supports enums without fields",
+        ));
+    }
+
+    let ident = &item_enum.ident;
+    let prefix = args
+        .prefix
+        .unwrap_or_else(|| format!("{}_", screaming_snake_case(&ident.to_string())));
+
+    let variants = item_enum
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+    let constant_names = variants
+        .iter()
+        .map(|variant| format!("{}{}", prefix, screaming_snake_case(&variant.to_string())))
+        .collect::<Vec<_>>();
+    let display_names = variants
+        .iter()
+        .map(|variant| variant.to_string())
+        .collect::<Vec<_>>();
+
+    Ok(quote! {
+        #item_enum
+
+        #derived
+        impl ::gdnative::export::EnumConstants for #ident {
+            fn constants() -> ::std::vec::Vec<(&'static str, i64)> {
+                ::std::vec![#((#constant_names, #ident::#variants as i64)),*]
+            }
+        }
+
+        #derived
+        impl ::gdnative::export::Export for #ident {
+            type Hint = ::gdnative::export::hint::IntHint<i64>;
+
+            fn export_info(hint: ::std::option::Option<Self::Hint>) -> ::gdnative::export::ExportInfo {
+                hint.unwrap_or_else(|| {
+                    ::gdnative::export::hint::IntHint::Enum(
+                        ::gdnative::export::hint::EnumHint::with_numbers(::std::vec![
+                            #((::std::string::String::from(#display_names), #ident::#variants as i64)),*
+                        ]),
+                    )
+                })
+                .export_info()
+            }
+        }
+
+        #derived
+        impl ::gdnative::core_types::ToVariant for #ident {
+            fn to_variant(&self) -> ::gdnative::core_types::Variant {
+                let value = match self {
+                    #(#ident::#variants => #ident::#variants as i64,)*
+                };
+                ::gdnative::core_types::ToVariant::to_variant(&value)
+            }
+        }
+
+        #derived
+        impl ::gdnative::core_types::FromVariant for #ident {
+            fn from_variant(
+                variant: &::gdnative::core_types::Variant,
+            ) -> ::std::result::Result<Self, ::gdnative::core_types::FromVariantError> {
+                let value = <i64 as ::gdnative::core_types::FromVariant>::from_variant(variant)?;
+                #(
+                    if value == #ident::#variants as i64 {
+                        return ::std::result::Result::Ok(#ident::#variants);
+                    }
+                )*
+                ::std::result::Result::Err(::gdnative::core_types::FromVariantError::UnknownEnumVariant {
+                    variant: ::std::string::ToString::to_string(&value),
+                    expected: &[#(#constant_names),*],
+                })
+            }
+        }
+    })
+}
+
+/// Converts a `CamelCase` identifier to `SCREAMING_SNAKE_CASE`, e.g. `HttpState` to `HTTP_STATE`
+/// and `IOError` to `IO_ERROR`.
+fn screaming_snake_case(ident: &str) -> String {
+    let chars = ident.chars().collect::<Vec<_>>();
+    let mut out = String::with_capacity(ident.len() + 4);
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).map_or(false, |next| next.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_uppercase());
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_enum_constant_names() {
+        assert_eq!(screaming_snake_case("Idle"), "IDLE");
+        assert_eq!(screaming_snake_case("HttpState"), "HTTP_STATE");
+        assert_eq!(screaming_snake_case("IOError"), "IO_ERROR");
+        assert_eq!(screaming_snake_case("Level2Boss"), "LEVEL2_BOSS");
+        assert_eq!(screaming_snake_case("ALREADY_UPPER"), "ALREADY_UPPER");
+    }
+
+    #[test]
+    fn export_enum_prefix() {
+        let item: ItemEnum = parse_quote! {
+            enum State {
+                Idle,
+                Running = 10,
+            }
+        };
+        let args = vec![parse_quote!(prefix = "MY_")];
+
+        let tokens = derive_export_enum(args, item).unwrap();
+        let output = tokens.to_string();
+        assert!(output.contains("\"MY_IDLE\""));
+        assert!(output.contains("\"MY_RUNNING\""));
+        assert!(syn::parse2::<syn::File>(tokens).is_ok());
+    }
+
+    #[test]
+    fn export_enum_hint() {
+        let item: ItemEnum = parse_quote! {
+            enum State {
+                Idle,
+                Running = 10,
+            }
+        };
+
+        let output = derive_export_enum(Vec::new(), item).unwrap().to_string();
+        assert!(output.contains("impl :: gdnative :: export :: Export for State"));
+        assert!(output
+            .contains("(:: std :: string :: String :: from (\"Idle\") , State :: Idle as i64)"));
+        assert!(output.contains(
+            "(:: std :: string :: String :: from (\"Running\") , State :: Running as i64)"
+        ));
+    }
+
+    #[test]
+    fn export_enum_rejects_fields() {
+        let item: ItemEnum = parse_quote! {
+            enum State {
+                Idle,
+                Running(f32),
+            }
+        };
+
+        assert!(derive_export_enum(Vec::new(), item).is_err());
+    }
+}
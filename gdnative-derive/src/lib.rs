@@ -6,9 +6,11 @@ extern crate syn;
 extern crate quote;
 
 use proc_macro::TokenStream;
-use proc_macro2::TokenStream as TokenStream2;
-use syn::{AttributeArgs, DeriveInput, ItemFn, ItemImpl};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{AttributeArgs, DeriveInput, ItemEnum, ItemFn, ItemImpl};
 
+mod export_enum;
 mod extend_bounds;
 mod methods;
 mod native_script;
@@ -52,6 +54,7 @@ mod variant;
 ///         use gdnative::export::*;
 ///         builder.method("foo", gdnative::export::godot_wrap_method!(Foo, false, fn foo(&self, #[base] _base: &Reference, bar: i64) -> i64))
 ///             .with_rpc_mode(RpcMode::Disabled)
+///             .with_typed_arg::<i64>("bar")
 ///             .done_stateless();
 ///     }
 /// }
@@ -61,18 +64,68 @@ mod variant;
 ///     }
 /// }
 /// ```
-/// **Important**: Only one `impl` block per struct may be attributed with `#[methods]`.
+/// The names and types of the arguments, other than `self` and the base object, are reported to
+/// Godot and show up in the editor and in `Object::get_method_list`. Arguments whose types don't
+/// implement `Export` are reported as untyped, and leading underscores are removed from names.
+///
+/// **Important**: Only one `impl` block per struct may be attributed with a plain `#[methods]`.
+///
+/// ## Mixins
+///
+/// Methods from other `impl` blocks, including `impl SomeTrait for MyClass` blocks, can be
+/// exported with `#[methods(mixin = "Name")]`. Instead of implementing `NativeClassMethods`,
+/// this generates a type `Name` implementing [`Mixin`][gdnative::export::Mixin], which is
+/// added to the class with the `#[mixins(Name)]` attribute of `#[derive(NativeClass)]` or
+/// with `ClassBuilder::mixin`. The type is private to the module, unless `pub` is added:
+/// `#[methods(mixin = "Name", pub)]`.
+///
+/// ```
+/// use gdnative::prelude::*;
+///
+/// trait Damageable {
+///     fn take_damage(&mut self, amount: i64);
+/// }
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// #[mixins(PlayerDamageable, PlayerInventory)]
+/// #[no_constructor]
+/// struct Player {
+///     health: i64,
+///     items: Vec<String>,
+/// }
+///
+/// #[methods]
+/// impl Player {}
+///
+/// #[methods(mixin = "PlayerDamageable")]
+/// impl Damageable for Player {
+///     #[method]
+///     fn take_damage(&mut self, amount: i64) {
+///         self.health -= amount;
+///     }
+/// }
+///
+/// #[methods(mixin = "PlayerInventory")]
+/// impl Player {
+///     #[method]
+///     fn add_item(&mut self, item: String) {
+///         self.items.push(item);
+///     }
+/// }
+/// ```
 ///
 /// For more context, please refer to [gdnative::derive::NativeClass](NativeClass).
 #[proc_macro_attribute]
 pub fn methods(meta: TokenStream, input: TokenStream) -> TokenStream {
-    if syn::parse::<syn::parse::Nothing>(meta.clone()).is_err() {
-        let err = syn::Error::new_spanned(
-            TokenStream2::from(meta),
-            "#[methods] does not take parameters.",
-        );
-        return error_with_input(input, err);
-    }
+    let args = match Punctuated::<syn::NestedMeta, Token![,]>::parse_terminated
+        .parse(meta)
+        .map_err(|err| syn::Error::new(err.span(), methods::INVALID_METHODS_ARGS))
+        .and_then(|args| methods::parse_methods_args(args.into_iter().collect()))
+    {
+        Ok(args) => args,
+        Err(err) => return error_with_input(input, err),
+    };
 
     let impl_block = match syn::parse::<ItemImpl>(input.clone()) {
         Ok(impl_block) => impl_block,
@@ -85,7 +138,7 @@ pub fn methods(meta: TokenStream, input: TokenStream) -> TokenStream {
         err
     }
 
-    TokenStream::from(methods::derive_methods(impl_block))
+    TokenStream::from(methods::derive_methods(impl_block, args))
 }
 
 /// Makes a function profiled in Godot's built-in profiler. This macro automatically
@@ -129,6 +182,72 @@ pub fn profiled(meta: TokenStream, input: TokenStream) -> TokenStream {
     }
 }
 
+/// Exports the variants of a fieldless enum as integer constants.
+///
+/// Implements `EnumConstants`, so the variants can be registered on a `NativeClass` with
+/// `#[export_enums(...)]` or `ClassBuilder::enum_constants`. Also implements `ToVariant` and
+/// `FromVariant` using the integer value of each variant, so the enum can be used directly in
+/// the signatures of exported methods.
+///
+/// The enum also implements `Export`, so it can be used as the type of a `#[property]`. It is
+/// shown in the inspector as a dropdown of the variant names, using the explicit discriminants
+/// where present. Values that do not match any variant are rejected when the property is set.
+///
+/// Constant names are the variant names in `SCREAMING_SNAKE_CASE`, prefixed by the enum name
+/// in the same case. A different prefix can be set with `#[export_enum(prefix = "...")]`, or
+/// removed with `#[export_enum(prefix = "")]`.
+///
+/// # Examples
+///
+/// ```
+/// use gdnative::prelude::*;
+///
+/// #[export_enum]
+/// #[derive(Copy, Clone, PartialEq)]
+/// enum State {
+///     Idle,
+///     Running = 10,
+/// }
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// // Registers STATE_IDLE = 0 and STATE_RUNNING = 10
+/// #[export_enums(State)]
+/// struct Player {
+///     // Shown as a dropdown with "Idle" and "Running" in the inspector
+///     #[property]
+///     state: State,
+/// }
+///
+/// #[methods]
+/// impl Player {
+///     fn new(_base: &Node) -> Self {
+///         Player { state: State::Idle }
+///     }
+///
+///     #[method]
+///     fn set_state(&mut self, state: State) {
+///         self.state = state;
+///     }
+/// }
+/// ```
+///
+/// GDScript can then use `player.set_state(player.STATE_RUNNING)`.
+///
+/// NativeScript has no API for class-level constants or static methods, so the constants are
+/// registered as read-only properties of each instance. They cannot be read through the script
+/// resource, like `Player.STATE_RUNNING`, and static methods cannot be exported at all.
+#[proc_macro_attribute]
+pub fn export_enum(meta: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(meta as AttributeArgs);
+    let item_enum = parse_macro_input!(input as ItemEnum);
+
+    match export_enum::derive_export_enum(args, item_enum) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 /// Makes it possible to use a type as a NativeScript.
 ///
 /// ## Type attributes
@@ -185,6 +304,15 @@ pub fn profiled(meta: TokenStream, input: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// ### `#[export_enums(path::to::Enum, ...)]`
+///
+/// Registers the variants of each listed enum as integer constants on the class. The enums
+/// must implement `EnumConstants`, usually through the [`#[export_enum]`](macro@export_enum)
+/// attribute.
+///
+/// NativeScript has no notion of class-level constants, so each constant is registered as a
+/// read-only property, available on every instance of the class: `node.STATE_IDLE`.
+///
 /// ### `#[no_constructor]`
 ///
 /// Indicates that this type has no zero-argument constructor. Instances of such
@@ -193,6 +321,93 @@ pub fn profiled(meta: TokenStream, input: TokenStream) -> TokenStream {
 ///
 /// See documentation on `Instance::emplace` for an example on how this can be used.
 ///
+/// ### `#[mixins(Mixin1, Mixin2, ...)]`
+///
+/// Registers the members of the listed [`Mixin`][gdnative::export::Mixin] types on the class,
+/// in addition to the ones of its `#[methods]` impl block. Mixins are usually generated by
+/// `#[methods(mixin = "Name")]`, see [`methods`](macro@methods) for an example.
+///
+/// ### `#[lifecycle_hooks]`
+///
+/// Wires the [`LifecycleHooks`][gdnative::export::LifecycleHooks] implementation of the type
+/// to the `_notification` virtual method, so hooks like `on_ready` and `on_predelete` are
+/// called when the owner receives the corresponding notifications. The type must implement
+/// `LifecycleHooks`, and its user-data wrapper must support `&mut self` access. `_notification`
+/// must not also be exported with `#[method]`.
+///
+/// ```
+/// use gdnative::prelude::*;
+/// use gdnative::export::LifecycleHooks;
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// #[lifecycle_hooks]
+/// #[no_constructor]
+/// struct Emitter {
+///     particles: Option<Rid>,
+/// }
+///
+/// impl LifecycleHooks for Emitter {
+///     fn on_predelete(&mut self, _owner: TRef<Node>) {
+///         if let Some(_rid) = self.particles.take() {
+///             // Free the server-side resource while the owner is still alive
+///         }
+///     }
+/// }
+/// ```
+///
+/// ### `#[super_class]`
+///
+/// Marks a field of type [`Super<T>`][gdnative::export::Super] holding the data of the Rust
+/// class `T` that this type extends. The type then implements
+/// [`NativeSubClass`][gdnative::export::NativeSubClass], and the properties, methods and
+/// signals registered by `T` are also registered for this type, before its own. `T` must have a
+/// `#[methods]` impl block. Only one field can be marked.
+///
+/// ```
+/// use gdnative::prelude::*;
+/// use gdnative::export::Super;
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// #[no_constructor]
+/// struct Weapon {
+///     #[property]
+///     damage: i64,
+/// }
+///
+/// #[methods]
+/// impl Weapon {}
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// #[no_constructor]
+/// struct Sword {
+///     // Sword also has the `damage` property
+///     #[super_class]
+///     weapon: Super<Weapon>,
+/// }
+/// ```
+///
+/// ### `#[auto_register]` / `#[auto_register(tool)]`
+///
+/// Submits the class for automatic registration, so it is added by
+/// `InitHandle::add_all_classes` instead of a manual `InitHandle::add_class` call. With
+/// `tool`, the class is added as a tool class. Requires the `inventory` feature of `gdnative`,
+/// and is not supported for generic types.
+///
+/// ```ignore
+/// #[derive(NativeClass)]
+/// #[auto_register]
+/// struct Player;
+///
+/// fn init(handle: InitHandle) {
+///     handle.add_all_classes();
+/// }
+///
+/// godot_init!(init);
+/// ```
+///
 ///
 /// ## Field attributes
 ///
@@ -208,11 +423,27 @@ pub fn profiled(meta: TokenStream, input: TokenStream) -> TokenStream {
 ///   Puts the property under the `my_category` category and renames it to
 ///   `my_property_name` in the inspector and for GDScript.
 ///
+/// - `group = "Movement"`
+///
+///   Shows the property in the collapsible `Movement` group of the inspector. Consecutive
+///   properties with the same group share a single group header, so fields in a group must
+///   be declared next to each other. Fields without a group can be declared before the first
+///   group or after the last one.
+///
 /// - `default = 42.0`
 ///
 ///   Sets the default value *in the inspector* for this property. The setter is *not*
 ///   guaranteed to be called by the engine with the value.
 ///
+/// - `hint = "NodePathHint::valid_types::<Node2D>()"`
+///
+///   Sets the editor hint of the property. The value can either be the path of a function
+///   returning the hint, like `hint = "Self::my_hint"`, or an expression whose value can be
+///   converted into the hint type of the property with `Into`. For example, an integer
+///   property can use `hint = "FlagsHint::from_bitflags(MyFlags::from_bits)"` or
+///   `hint = "IntHint::Layers2DPhysics"`. Functions must return the hint type itself. Types in
+///   the expression are resolved at the place of the struct, so they must be imported there.
+///
 /// - `get` / `get_ref` / `set`
 ///
 ///   Configure getter/setter for property. All of them can accept a path to specify a custom
@@ -232,6 +463,68 @@ pub fn profiled(meta: TokenStream, input: TokenStream) -> TokenStream {
 ///
 ///   Hides the property from the editor. Does not prevent it from being sent over network or saved in storage.
 ///
+/// - `nested`
+///
+///   Registers the fields of a struct implementing
+///   [`NestedProperties`][gdnative::export::NestedProperties] as separate properties, named
+///   `field_name/sub_field_name`. Hints and other options are set on the fields of the nested
+///   type instead. Can only be combined with `path` and `group`. See
+///   [`NestedProperties`](macro@NestedProperties) for an example.
+///
+/// - `notify = "health_changed"`
+///
+///   Emits the `health_changed` signal with the new value whenever the property is changed
+///   through its setter. The signal must be registered separately, e.g. with `#[signal]`.
+///   To validate values before they are stored, use
+///   [`PropertyBuilder::with_validator`][gdnative::export::PropertyBuilder::with_validator]
+///   from a `register_with` function instead.
+///
+/// ### `#[signal]`
+///
+/// Registers a field of type [`Signal<Args>`][gdnative::export::Signal] as a signal, where `Args` is
+/// a tuple of the parameter types, e.g. `Signal<(i64, Vector2)>`. Each parameter type must implement
+/// [`Export`][gdnative::export::Export], which is used to report the parameter types to the editor.
+///
+/// For every signal, the following associated functions are generated on the class, with the same
+/// visibility as the field:
+///
+/// - `fn emit_<field>(owner: &Base, <params>...)` emits the signal with typed arguments.
+/// - `fn connect_<field>(owner: &Base, target, method) -> GodotResult` connects the signal to
+///   `method` on `target`.
+///
+/// Renaming the field or changing the parameter types thus results in compile errors at the
+/// usage sites, instead of silent failures at runtime. Possible arguments for the attribute are:
+///
+/// - `name = "signal_name"`
+///
+///   Registers the signal under a different name than the field.
+///
+/// - `params = "height, direction"`
+///
+///   Names the signal parameters, in the same order as the types in `Args`. If omitted, the
+///   parameters are named `arg0`, `arg1` and so on.
+///
+/// ```
+/// use gdnative::prelude::*;
+/// use gdnative::export::Signal;
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// #[no_constructor]
+/// struct Player {
+///     #[signal(params = "damage, source")]
+///     hit: Signal<(i64, GodotString)>,
+/// }
+///
+/// #[methods]
+/// impl Player {
+///     #[method]
+///     fn take_damage(&self, #[base] base: &Node, damage: i64) {
+///         Self::emit_hit(base, damage, "trap".into());
+///     }
+/// }
+/// ```
+///
 /// ### `#[methods]`
 /// Adds the necessary information to a an `impl` block to register the properties and methods with Godot.
 ///
@@ -252,7 +545,10 @@ pub fn profiled(meta: TokenStream, input: TokenStream) -> TokenStream {
 /// - Any number of required parameters, which must have the type `Variant` or must implement the `FromVariant` trait.
 ///  `FromVariant` is implemented for most common types.
 /// - Any number of optional parameters annotated with `#[opt]`. Same rules as for required parameters apply.
-///   Optional parameters must appear at the end of the parameter list.
+///   Optional parameters must appear at the end of the parameter list. Missing optional arguments are
+///   `Default::default()`, unless a default is given with `#[opt(default = expr)]`. The expression is
+///   evaluated on each call where the argument is missing. Godot 3's NativeScript API cannot describe
+///   default values, so they are not visible to the editor or `get_method_list`.
 /// - Return values must implement the `OwnedToVariant` trait (automatically implemented by `ToVariant`)
 ///   or be a `Variant` type.
 ///
@@ -307,12 +603,29 @@ pub fn profiled(meta: TokenStream, input: TokenStream) -> TokenStream {
 ///   }
 ///   ```
 ///
+/// - `virtual`
+///
+///   Marks the method as an override of a virtual method of the base class, such as `_ready` or
+///   `_process`. The name and signature are checked against the engine at compile time, so typos
+///   like `_proces` or a wrong parameter type result in a compile error instead of a method that
+///   is silently never called. Parameter and return types must be compatible with the engine's,
+///   as described in [`VirtualType`][gdnative::export::VirtualType].
+///
+///   For example:
+///   ```ignore
+///   #[method(virtual)]
+///   fn _process(&mut self, #[base] base: &Node, delta: f64) {
+///       // ...
+///   }
+///   ```
+///
 ///
 /// #### `Node` virtual functions
 ///
 /// This is a list of common Godot virtual functions that are automatically called via [notifications](https://docs.godotengine.org/en/stable/classes/class_object.html#class-object-method-notification).
 ///
 /// It is assumed that every method is exported via `#[method]` attribute. The parameter `#[base] base: &Node` can be omitted if you don't need it.
+/// Use `#[method(virtual)]` to have their names and signatures checked at compile time.
 ///
 /// ```ignore
 /// fn _ready(&self, #[base] base: &Node);
@@ -410,7 +723,19 @@ pub fn profiled(meta: TokenStream, input: TokenStream) -> TokenStream {
 /// <br><br>
 #[proc_macro_derive(
     NativeClass,
-    attributes(inherit, register_with, no_constructor, user_data, property)
+    attributes(
+        inherit,
+        register_with,
+        no_constructor,
+        user_data,
+        property,
+        signal,
+        export_enums,
+        mixins,
+        lifecycle_hooks,
+        super_class,
+        auto_register
+    )
 )]
 pub fn derive_native_class(input: TokenStream) -> TokenStream {
     // Converting the proc_macro::TokenStream into non proc_macro types so that tests
@@ -435,6 +760,56 @@ pub fn derive_native_class(input: TokenStream) -> TokenStream {
     TokenStream::from(derived)
 }
 
+/// Allows the fields of a struct to be exported as properties of a `NativeClass` containing it.
+///
+/// Implements [`NestedProperties`][gdnative::export::NestedProperties]. Fields marked with
+/// `#[property]` are registered as properties named `prefix/field_name`, where the prefix is
+/// the name of the `#[property(nested)]` field holding the struct. The `path`, `default`,
+/// `hint`, `no_editor` and `nested` arguments of [`#[property]`](macro@NativeClass)
+/// can be used on the fields, so structs can be nested in each other.
+///
+/// # Examples
+///
+/// ```
+/// use gdnative::prelude::*;
+/// use gdnative::export::hint::{FloatHint, RangeHint};
+///
+/// #[derive(NestedProperties)]
+/// struct Movement {
+///     #[property(hint = "FloatHint::Range(RangeHint::new(0.0, 500.0))")]
+///     speed: f64,
+///     #[property(default = 2)]
+///     jumps: i64,
+/// }
+///
+/// #[derive(NestedProperties)]
+/// struct Config {
+///     #[property]
+///     name: String,
+///     // Registers `config/movement/speed` and `config/movement/jumps`
+///     #[property(nested)]
+///     movement: Movement,
+/// }
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// #[no_constructor]
+/// struct Player {
+///     // Registers `config/name` and the properties of `movement`
+///     #[property(nested)]
+///     config: Config,
+/// }
+/// ```
+#[proc_macro_derive(NestedProperties, attributes(property))]
+pub fn derive_nested_properties(input: TokenStream) -> TokenStream {
+    let derive_input = syn::parse_macro_input!(input as DeriveInput);
+
+    match native_script::derive_nested_properties(&derive_input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 #[proc_macro_derive(ToVariant, attributes(variant))]
 pub fn derive_to_variant(input: TokenStream) -> TokenStream {
     match variant::derive_to_variant(variant::ToVariantTrait::ToVariant, input) {
@@ -471,8 +846,12 @@ pub fn derive_from_variant(input: TokenStream) -> TokenStream {
 ///     foo: i32,
 ///     bar: String,
 ///     #[opt] baz: Option<Ref<Node>>,
+///     #[opt(default = 1.0)] scale: f32,
 /// }
 /// ```
+///
+/// Missing `#[opt]` arguments are set to `Default::default()`, or to the expression given with
+/// `#[opt(default = expr)]`.
 #[proc_macro_derive(FromVarargs, attributes(opt))]
 pub fn derive_from_varargs(input: TokenStream) -> TokenStream {
     let derive_input = syn::parse_macro_input!(input as syn::DeriveInput);
@@ -482,6 +861,26 @@ pub fn derive_from_varargs(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Returns the text of the doc comments in `attrs`, without the leading space of each line,
+/// or `None` if there are none.
+fn doc_comment(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                lit: syn::Lit::Str(lit_str),
+                ..
+            })) => Some(lit_str.value()),
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+        .collect::<Vec<_>>();
+
+    let doc = lines.join("\n").trim().to_string();
+    (!doc.is_empty()).then(|| doc)
+}
+
 /// Returns a standard header for derived implementations.
 ///
 /// Adds the `automatically_derived` attribute and prevents common lints from triggering
@@ -1,4 +1,7 @@
-use syn::{spanned::Spanned, FnArg, ImplItem, ItemImpl, Pat, PatIdent, Signature, Type};
+use syn::{
+    spanned::Spanned, AttributeArgs, FnArg, ImplItem, ItemImpl, Meta, NestedMeta, Pat, PatIdent,
+    Signature, Type,
+};
 
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, ToTokens};
@@ -60,7 +63,9 @@ pub(crate) struct ExportMethod {
     pub(crate) sig: Signature,
     pub(crate) export_args: ExportArgs,
     pub(crate) optional_args: Option<usize>,
+    pub(crate) optional_defaults: Vec<Option<syn::Expr>>,
     pub(crate) exist_base_arg: bool,
+    pub(crate) documentation: Option<String>,
 }
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
@@ -69,10 +74,74 @@ pub(crate) struct ExportArgs {
     pub(crate) rpc_mode: Option<RpcMode>,
     pub(crate) name_override: Option<String>,
     pub(crate) is_deref_return: bool,
+    pub(crate) is_virtual: bool,
 }
 
-pub(crate) fn derive_methods(item_impl: ItemImpl) -> TokenStream2 {
+/// Arguments of the `#[methods]` attribute.
+#[derive(Default)]
+pub(crate) struct MethodsArgs {
+    /// Name of the `Mixin` type to generate instead of a `NativeClassMethods` impl.
+    pub(crate) mixin: Option<syn::Ident>,
+    /// Whether the mixin type is `pub`.
+    pub(crate) is_pub: bool,
+}
+
+/// Error message for arguments of `#[methods]` that are not understood.
+pub(crate) const INVALID_METHODS_ARGS: &str = "expected `mixin = \"Name\"` or `pub`";
+
+pub(crate) fn parse_methods_args(args: AttributeArgs) -> Result<MethodsArgs, syn::Error> {
+    let mut parsed = MethodsArgs::default();
+
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::NameValue(pair)) if pair.path.is_ident("mixin") => {
+                if parsed.mixin.is_some() {
+                    return Err(syn::Error::new(
+                        pair.span(),
+                        "`mixin` was set more than once",
+                    ));
+                }
+
+                let name = match &pair.lit {
+                    syn::Lit::Str(lit_str) => lit_str.parse::<syn::Ident>()?,
+                    lit => {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "`mixin` should be the name of the type to generate",
+                        ))
+                    }
+                };
+                parsed.mixin = Some(name);
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("pub") => {
+                parsed.is_pub = true;
+            }
+            arg => return Err(syn::Error::new(arg.span(), INVALID_METHODS_ARGS)),
+        }
+    }
+
+    if parsed.is_pub && parsed.mixin.is_none() {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`pub` is only valid together with `mixin`",
+        ));
+    }
+
+    Ok(parsed)
+}
+
+pub(crate) fn derive_methods(item_impl: ItemImpl, args: MethodsArgs) -> TokenStream2 {
     let derived = crate::automatically_derived();
+
+    // Methods of trait impls are called with method call syntax, which needs the trait in scope
+    let use_trait = item_impl.trait_.as_ref().map(|(_, path, _)| {
+        let mut path = path.clone();
+        for segment in path.segments.iter_mut() {
+            segment.arguments = syn::PathArguments::None;
+        }
+        quote!(#[allow(unused_imports)] use #path as _;)
+    });
+
     let (impl_block, export) = impl_gdnative_expose(item_impl);
 
     let class_name = export.class_ty;
@@ -82,7 +151,7 @@ pub(crate) fn derive_methods(item_impl: ItemImpl) -> TokenStream2 {
     let methods = export
         .methods
         .into_iter()
-        .map(|ExportMethod { sig, export_args, optional_args, exist_base_arg}| {
+        .map(|ExportMethod { sig, export_args, optional_args, optional_defaults, exist_base_arg, documentation }| {
             let sig_span = sig.ident.span();
 
             let name = sig.ident;
@@ -137,10 +206,66 @@ pub(crate) fn derive_methods(item_impl: ItemImpl) -> TokenStream2 {
                 else if n < arg_count - optional_args {
                     quote_spanned!(span => #arg ,)
                 } else {
-                    quote_spanned!(span => #[opt] #arg ,)
+                    match &optional_defaults[n - (arg_count - optional_args)] {
+                        Some(default) => quote_spanned!(span => #[opt(default = #default)] #arg ,),
+                        None => quote_spanned!(span => #[opt] #arg ,),
+                    }
                 }
             });
 
+            // Argument names and types reported to Godot, excluding self and base
+            let arg_infos = sig.inputs.iter().enumerate().filter_map(|(n, arg)| {
+                let arg = match arg {
+                    FnArg::Typed(arg) if n > 0 && !(exist_base_arg && n == 1) => arg,
+                    _ => return None,
+                };
+
+                let ty = &arg.ty;
+                let arg_name = match &*arg.pat {
+                    Pat::Ident(pat_ident) => {
+                        // Leading underscores only silence unused variable warnings in Rust
+                        let name = pat_ident.ident.to_string();
+                        let trimmed = name.trim_start_matches('_');
+                        if trimmed.is_empty() { name } else { trimmed.to_string() }
+                    }
+                    _ => format!("arg{}", n),
+                };
+
+                Some(quote_spanned!(arg.span() =>
+                    .with_arg(#arg_name, (&::gdnative::export::ArgInfoProbe::<#ty>::new()).arg_export_info())
+                ))
+            }).collect::<Vec<_>>();
+
+            // Checks the name and signature against the `*Virtual` traits of the base class,
+            // which are implemented for all its subclasses.
+            let check_virtual = if export_args.is_virtual {
+                let virtual_name = match syn::parse_str::<syn::Ident>(&name_string) {
+                    Ok(ident) => syn::Ident::new(&ident.to_string(), sig_span),
+                    Err(_) => {
+                        let message = format!("`{}` is not a virtual method name", name_string);
+                        return syn::Error::new(sig_span, message).to_compile_error();
+                    }
+                };
+                let arg_tys = sig.inputs.iter().enumerate().filter_map(|(n, arg)| match arg {
+                    FnArg::Typed(arg) if n > 0 && !(exist_base_arg && n == 1) => Some(&arg.ty),
+                    _ => None,
+                });
+
+                Some(quote_spanned!(sig_span=>
+                    {
+                        #[allow(unused_imports)]
+                        use ::gdnative::api::virtuals::*;
+                        <<#class_name as ::gdnative::export::NativeClass>::Base>::#virtual_name(
+                            ::std::marker::PhantomData::<fn( #( #arg_tys ),* ) -> #ret_ty>,
+                        );
+                    }
+                ))
+            } else {
+                None
+            };
+
+            let with_documentation = documentation.map(|doc| quote!(.with_documentation(#doc)));
+
             let warn_deprecated_export = if export_args.is_old_syntax {
                 Some(quote_spanned!(ret_span=> ::gdnative::export::deprecated_export_syntax!();))
             } else {
@@ -168,8 +293,11 @@ pub(crate) fn derive_methods(item_impl: ItemImpl) -> TokenStream2 {
 
                     #builder.method(#name_string, method)
                         .with_rpc_mode(#rpc)
+                        #( #arg_infos )*
+                        #with_documentation
                         .done_stateless();
 
+                    #check_virtual
                     #warn_deprecated_export
                     #warn_deprecated_ref_return
                 }
@@ -177,19 +305,45 @@ pub(crate) fn derive_methods(item_impl: ItemImpl) -> TokenStream2 {
         })
         .collect::<Vec<_>>();
 
-    quote::quote!(
-        #impl_block
+    match args.mixin {
+        Some(mixin) => {
+            let vis = args.is_pub.then(|| quote!(pub));
+            let doc = format!(
+                "Mixin exporting the methods of an `impl` block of `{}`.",
+                quote!(#class_name),
+            );
 
-        #derived
-        impl gdnative::export::NativeClassMethods for #class_name {
-            fn register(#builder: &::gdnative::export::ClassBuilder<Self>) {
-                use gdnative::export::*;
+            quote::quote!(
+                #impl_block
 
-                #(#methods)*
-            }
+                #[doc = #doc]
+                #vis struct #mixin;
+
+                #derived
+                impl ::gdnative::export::Mixin<#class_name> for #mixin {
+                    fn register(#builder: &::gdnative::export::ClassBuilder<#class_name>) {
+                        use gdnative::export::*;
+                        #use_trait
+
+                        #(#methods)*
+                    }
+                }
+            )
         }
+        None => quote::quote!(
+            #impl_block
+
+            #derived
+            impl gdnative::export::NativeClassMethods for #class_name {
+                fn register(#builder: &::gdnative::export::ClassBuilder<Self>) {
+                    use gdnative::export::*;
+                    #use_trait
 
-    )
+                    #(#methods)*
+                }
+            }
+        ),
+    }
 }
 
 /// Extract the data to export from the impl block.
@@ -361,6 +515,21 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
                                     } else {
                                         export_args.is_deref_return = true;
                                     }
+                                } else if path.is_ident("virtual") {
+                                    // override of an engine virtual method
+                                    if lit.is_some() {
+                                        errors.push(syn::Error::new(
+                                            nested_meta.span(),
+                                            "`virtual` does not take any values",
+                                        ));
+                                    } else if export_args.is_virtual {
+                                        errors.push(syn::Error::new(
+                                            nested_meta.span(),
+                                            "`virtual` was set more than once",
+                                        ));
+                                    } else {
+                                        export_args.is_virtual = true;
+                                    }
                                 } else {
                                     let msg = format!(
                                         "unknown option for #[{}]: `{}`",
@@ -379,6 +548,7 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
 
                 if let Some(export_args) = export_args.take() {
                     let mut optional_args = None;
+                    let mut optional_defaults = Vec::new();
                     let mut exist_base_arg = false;
 
                     for (n, arg) in method.sig.inputs.iter_mut().enumerate() {
@@ -389,10 +559,15 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
 
                         let mut is_optional = false;
                         let mut is_base = false;
+                        let mut default = None;
 
                         attrs.retain(|attr| {
                             if attr.path.is_ident("opt") {
                                 is_optional = true;
+                                match crate::varargs::parse_opt_default(attr) {
+                                    Ok(parsed) => default = parsed,
+                                    Err(err) => errors.push(err),
+                                }
                                 false
                             } else if attr.path.is_ident("base") {
                                 is_base = true;
@@ -415,6 +590,7 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
                                 ));
                             } else {
                                 *optional_args.get_or_insert(0) += 1;
+                                optional_defaults.push(default);
                             }
                         } else if optional_args.is_some() {
                             errors.push(syn::Error::new(
@@ -438,7 +614,9 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
                         sig: method.sig.clone(),
                         export_args,
                         optional_args,
+                        optional_defaults,
                         exist_base_arg,
+                        documentation: crate::doc_comment(&method.attrs),
                     });
                 }
 
@@ -524,3 +702,72 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
 
     (result, export)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn derive_virtual_method() {
+        let input = parse_quote! {
+            impl Foo {
+                #[method(virtual)]
+                fn _process(&mut self, #[base] base: &Node, delta: f64) {}
+            }
+        };
+        let output = derive_methods(input, MethodsArgs::default()).to_string();
+        assert!(output.contains("virtuals"));
+        assert!(output.contains("_process"));
+        assert!(!output.contains("compile_error"));
+
+        let input = parse_quote! {
+            impl Foo {
+                #[method(virtual, virtual)]
+                fn _ready(&self) {}
+            }
+        };
+        let output = derive_methods(input, MethodsArgs::default()).to_string();
+        assert!(output.contains("`virtual` was set more than once"));
+    }
+
+    #[test]
+    fn derive_method_documentation() {
+        let input = parse_quote! {
+            impl Foo {
+                /// Deals `amount` damage.
+                ///
+                /// Returns the remaining health.
+                #[method]
+                fn take_damage(&mut self, amount: i64) -> i64 {}
+            }
+        };
+        let output = derive_methods(input, MethodsArgs::default()).to_string();
+        assert!(output.contains(
+            r#"with_documentation ("Deals `amount` damage.\n\nReturns the remaining health.")"#
+        ));
+    }
+
+    #[test]
+    fn derive_mixin() {
+        let args = parse_methods_args(vec![
+            parse_quote!(mixin = "FooDamageable"),
+            parse_quote!(pub),
+        ])
+        .unwrap();
+        let input = parse_quote! {
+            impl traits::Damageable<i64> for Foo {
+                #[method]
+                fn take_damage(&mut self, amount: i64) {}
+            }
+        };
+        let output = derive_methods(input, args).to_string();
+        assert!(output.contains("pub struct FooDamageable"));
+        assert!(output.contains("Mixin < Foo > for FooDamageable"));
+        assert!(output.contains("use traits :: Damageable as _"));
+        assert!(!output.contains("NativeClassMethods"));
+
+        assert!(parse_methods_args(vec![parse_quote!(pub)]).is_err());
+        assert!(parse_methods_args(vec![parse_quote!(mixin)]).is_err());
+    }
+}
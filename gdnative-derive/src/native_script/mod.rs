@@ -1,19 +1,41 @@
+use std::collections::HashSet;
+
 use proc_macro2::TokenStream as TokenStream2;
 
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::{Data, DeriveInput, Expr, Fields, Ident, Meta, MetaList, NestedMeta, Path, Stmt, Type};
 
+mod nested_properties;
 mod property_args;
+mod signal_args;
+pub(crate) use nested_properties::derive_nested_properties;
 use property_args::{PropertyAttrArgs, PropertyAttrArgsBuilder, PropertyGet, PropertySet};
+use signal_args::{SignalAttrArgsBuilder, SignalDecl};
 
 pub(crate) struct DeriveData {
     pub(crate) name: Ident,
+    pub(crate) documentation: Option<String>,
     pub(crate) godot_name: Option<String>,
     pub(crate) base: Type,
     pub(crate) register_callback: Option<Path>,
     pub(crate) user_data: Type,
     pub(crate) properties: Vec<(Ident, PropertyAttrArgs)>,
+    pub(crate) signals: Vec<SignalDecl>,
+    pub(crate) enums: Vec<Path>,
+    pub(crate) mixins: Vec<Path>,
     pub(crate) no_constructor: bool,
+    pub(crate) lifecycle_hooks: bool,
+    pub(crate) super_class: Option<(Ident, Type)>,
+    pub(crate) auto_register: Option<AutoRegister>,
+}
+
+/// How a class is added by `InitHandle::add_all_classes`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AutoRegister {
+    Class,
+    ToolClass,
 }
 
 pub(crate) fn impl_empty_nativeclass(derive_input: &DeriveInput) -> TokenStream2 {
@@ -60,15 +82,54 @@ pub(crate) fn derive_native_class(derive_input: &DeriveInput) -> Result<TokenStr
             .register_callback
             .map(|function_path| quote!(#function_path(builder);))
             .unwrap_or(quote!({}));
-        let properties = data
+        let mut current_group = None;
+        // Group headers are registered as properties named after the group, so each group, as
+        // well as the unnamed header ending a group, may only be registered once per class
+        let mut used_groups = HashSet::new();
+        let mut group_ended = false;
+        let mut properties = data
             .properties
             .into_iter()
             .map(|(ident, config)| {
+                // Start a new inspector group whenever it differs from the previous property's
+                let group_header = if config.group != current_group {
+                    match &config.group {
+                        Some(group) if !used_groups.insert(group.clone()) => {
+                            return Err(syn::Error::new(
+                                ident.span(),
+                                format!(
+                                    "group `{}` is already used by an earlier field, fields in the same group must be declared next to each other",
+                                    group
+                                ),
+                            ));
+                        }
+                        Some(_) if group_ended => {
+                            return Err(syn::Error::new(
+                                ident.span(),
+                                "a group cannot start after fields without a group that follow another group, declare fields without a group before the first group or after the last one",
+                            ));
+                        }
+                        Some(_) => {}
+                        None => group_ended = true,
+                    }
+                    let group = config.group.clone().unwrap_or_default();
+                    Some(quote!(builder.property_group(#group, "");))
+                } else {
+                    None
+                };
+                current_group = config.group.clone();
+
+                if config.nested {
+                    return nested_property(&name, ident, config, group_header);
+                }
+
                 let with_default = config
                     .default
                     .map(|default_value| quote!(.with_default(#default_value)));
-                let with_hint = config.hint.map(|hint_fn| quote!(.with_hint(#hint_fn())));
+                let with_hint = config.hint.map(with_hint);
                 let with_usage = config.no_editor.then(|| quote!(.with_usage(::gdnative::export::PropertyUsage::NOEDITOR)));
+                let with_notify = config.notify.map(|signal| quote!(.with_notify(#signal)));
+                let with_documentation = config.documentation.map(|doc| quote!(.with_documentation(#doc)));
                 // check whether this property type is `Property<T>`. if so, extract T from it.
                 let property_ty = match config.ty {
                     Type::Path(ref path) => path
@@ -141,17 +202,108 @@ pub(crate) fn derive_native_class(derive_input: &DeriveInput) -> Result<TokenStr
 
                 let label = config.path.unwrap_or_else(|| format!("{}", ident));
                 Ok(quote!({
+                    #group_header
                     builder.property #property_ty(#label)
                         #with_default
                         #with_hint
                         #with_usage
                         #with_getter
                         #with_setter
+                        #with_notify
+                        #with_documentation
                         .done();
                 }))
             })
             .collect::<Result<Vec<_>, _>>()?;
 
+        // End the last group, so properties from `register_with` are not part of it
+        if current_group.is_some() {
+            properties.push(quote!(builder.property_group("", "");));
+        }
+
+        let signals = data.signals.iter().map(|signal| {
+            let name_str = &signal.name;
+            let with_params = signal.params.iter().map(|(param_name, param_ty)| {
+                let param_name = param_name.to_string();
+                quote!(.with_typed_param::<#param_ty>(#param_name))
+            });
+            let with_documentation = signal
+                .documentation
+                .as_ref()
+                .map(|doc| quote!(.with_documentation(#doc)));
+
+            quote!({
+                builder.signal(#name_str)
+                    #(#with_params)*
+                    #with_documentation
+                    .done();
+            })
+        });
+
+        let enum_constants = data
+            .enums
+            .iter()
+            .map(|path| quote!(builder.enum_constants::<#path>();));
+
+        let mixins = data
+            .mixins
+            .iter()
+            .map(|path| quote!(builder.mixin::<#path>();));
+
+        let signal_helpers = data.signals.iter().map(|signal| {
+            let SignalDecl { ident, vis, name, params, .. } = signal;
+            let emit_ident = Ident::new(&format!("emit_{}", ident.unraw()), ident.span());
+            let connect_ident = Ident::new(&format!("connect_{}", ident.unraw()), ident.span());
+            let param_names = params.iter().map(|(name, _)| name);
+            let param_names_again = params.iter().map(|(name, _)| name);
+            let param_types = params.iter().map(|(_, ty)| ty);
+
+            let emit_doc = format!("Emits the signal `{}` on `owner`.", name);
+            let connect_doc = format!(
+                "Connects the signal `{}` of `owner` to `method` on `target`.",
+                name,
+            );
+
+            quote! {
+                #[doc = #emit_doc]
+                #[allow(dead_code)]
+                #vis fn #emit_ident(owner: &#base, #(#param_names: #param_types),*) {
+                    ::gdnative::api::Object::emit_signal(
+                        owner,
+                        #name,
+                        &[#(::gdnative::core_types::OwnedToVariant::owned_to_variant(#param_names_again)),*],
+                    );
+                }
+
+                #[doc = #connect_doc]
+                #[allow(dead_code)]
+                #vis fn #connect_ident(
+                    owner: &#base,
+                    target: impl ::gdnative::object::AsArg<::gdnative::api::Object>,
+                    method: impl Into<::gdnative::core_types::GodotString>,
+                ) -> ::gdnative::core_types::GodotResult {
+                    ::gdnative::api::Object::connect(
+                        owner,
+                        #name,
+                        target,
+                        method,
+                        ::gdnative::core_types::VariantArray::new_shared(),
+                        0,
+                    )
+                }
+            }
+        }).collect::<Vec<_>>();
+
+        let maybe_signal_helpers = if signal_helpers.is_empty() {
+            None
+        } else {
+            Some(quote! {
+                impl #name {
+                    #(#signal_helpers)*
+                }
+            })
+        };
+
         let maybe_statically_named = data.godot_name.map(|name_str| {
             quote! {
                 #derived
@@ -171,6 +323,41 @@ pub(crate) fn derive_native_class(derive_input: &DeriveInput) -> Result<TokenStr
             })
         };
 
+        let lifecycle_hooks = data
+            .lifecycle_hooks
+            .then(|| quote!(builder.lifecycle_hooks();));
+
+        // Registered first, so the members of the class itself override the super class ones
+        let register_super_class = data
+            .super_class
+            .as_ref()
+            .map(|_| quote!(builder.super_class();));
+
+        let class_documentation = data
+            .documentation
+            .map(|doc| quote!(builder.class_documentation(#doc);));
+
+        let maybe_native_sub_class = data.super_class.map(|(field, super_ty)| {
+            quote! {
+                #derived
+                impl ::gdnative::export::NativeSubClass for #name {
+                    type Super = #super_ty;
+
+                    fn super_data(&self) -> &::gdnative::export::Super<Self::Super> {
+                        &self.#field
+                    }
+                }
+            }
+        });
+
+        let maybe_auto_register = data.auto_register.map(|auto_register| {
+            let add_fn = match auto_register {
+                AutoRegister::Class => quote!(add_class),
+                AutoRegister::ToolClass => quote!(add_tool_class),
+            };
+            quote!(::gdnative::export::godot_auto_register_class!(#name, #add_fn);)
+        });
+
         quote!(
             #derived
             impl ::gdnative::export::NativeClass for #name {
@@ -180,12 +367,21 @@ pub(crate) fn derive_native_class(derive_input: &DeriveInput) -> Result<TokenStr
                 #init
 
                 fn register_properties(builder: &::gdnative::export::ClassBuilder<Self>) {
+                    #register_super_class
+                    #class_documentation
+                    #(#enum_constants)*
+                    #(#signals)*
                     #(#properties)*;
+                    #(#mixins)*
+                    #lifecycle_hooks
                     #register_callback
                 }
             }
 
+            #maybe_native_sub_class
             #maybe_statically_named
+            #maybe_signal_helpers
+            #maybe_auto_register
         )
     };
 
@@ -237,6 +433,34 @@ fn parse_derive_input(input: &DeriveInput) -> Result<DeriveData, syn::Error> {
         .iter()
         .any(|a| a.path.is_ident("no_constructor"));
 
+    let lifecycle_hooks = input
+        .attrs
+        .iter()
+        .any(|a| a.path.is_ident("lifecycle_hooks"));
+
+    let auto_register = input
+        .attrs
+        .iter()
+        .find(|a| a.path.is_ident("auto_register"))
+        .map(|attr| parse_auto_register(input, attr))
+        .transpose()?;
+
+    let mut enums = Vec::new();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path.is_ident("export_enums"))
+    {
+        let paths = attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+        enums.extend(paths);
+    }
+
+    let mut mixins = Vec::new();
+    for attr in input.attrs.iter().filter(|a| a.path.is_ident("mixins")) {
+        let paths = attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+        mixins.extend(paths);
+    }
+
     // make sure it's a struct
     let struct_data = if let Data::Struct(data) = &input.data {
         data
@@ -247,68 +471,256 @@ fn parse_derive_input(input: &DeriveInput) -> Result<DeriveData, syn::Error> {
         ));
     };
 
-    // Find all fields with a `#[property]` attribute
+    // Find all fields with a `#[property]` or `#[signal]` attribute
     let mut properties = Vec::new();
+    let mut signals = Vec::new();
+    let mut super_class = None;
 
     if let Fields::Named(names) = &struct_data.fields {
         for field in &names.named {
             let mut property_args = None;
+            let mut signal_args = None;
 
             for attr in field.attrs.iter() {
-                if !attr.path.is_ident("property") {
-                    continue;
-                }
+                if attr.path.is_ident("signal") {
+                    let ident = field.ident.as_ref().ok_or_else(|| {
+                        syn::Error::new(field.ident.span(), "Fields should be named")
+                    })?;
+
+                    if signal_args.is_some() {
+                        return Err(syn::Error::new(
+                            attr.span(),
+                            "there can be only one `#[signal]` attribute per field",
+                        ));
+                    }
 
-                let meta = attr.parse_meta()?;
-
-                match meta {
-                    Meta::List(MetaList { nested, .. }) => {
-                        let attr_args_builder = property_args
-                            .get_or_insert_with(|| PropertyAttrArgsBuilder::new(&field.ty));
-
-                        for arg in nested {
-                            if let NestedMeta::Meta(Meta::NameValue(ref pair)) = arg {
-                                attr_args_builder.add_pair(pair)?;
-                            } else if let NestedMeta::Meta(Meta::Path(ref path)) = arg {
-                                attr_args_builder.add_path(path)?;
-                            } else {
-                                let msg = format!("Unexpected argument: {:?}", arg);
-                                return Err(syn::Error::new(arg.span(), msg));
+                    let mut builder = SignalAttrArgsBuilder::new(ident, &field.vis, &field.ty);
+                    match attr.parse_meta()? {
+                        Meta::List(MetaList { nested, .. }) => {
+                            for arg in nested {
+                                if let NestedMeta::Meta(Meta::NameValue(ref pair)) = arg {
+                                    builder.add_pair(pair)?;
+                                } else {
+                                    let msg = format!("Unexpected argument: {:?}", arg);
+                                    return Err(syn::Error::new(arg.span(), msg));
+                                }
                             }
                         }
+                        Meta::Path(_) => {}
+                        m => {
+                            let msg = format!("Unexpected meta variant: {:?}", m);
+                            return Err(syn::Error::new(m.span(), msg));
+                        }
                     }
-                    Meta::Path(_) => {
-                        property_args
-                            .get_or_insert_with(|| PropertyAttrArgsBuilder::new(&field.ty));
-                    }
-                    m => {
-                        let msg = format!("Unexpected meta variant: {:?}", m);
-                        return Err(syn::Error::new(m.span(), msg));
+
+                    signal_args = Some(builder);
+                    continue;
+                }
+
+                if attr.path.is_ident("super_class") {
+                    if super_class.is_some() {
+                        return Err(syn::Error::new(
+                            attr.span(),
+                            "there can be only one `#[super_class]` field",
+                        ));
                     }
+
+                    let ident = field.ident.clone().ok_or_else(|| {
+                        syn::Error::new(field.ident.span(), "Fields should be named")
+                    })?;
+                    super_class = Some((ident, parse_super_class_type(&field.ty)?));
+                    continue;
+                }
+
+                if attr.path.is_ident("property") {
+                    parse_property_attr(attr, &field.ty, &mut property_args)?;
                 }
             }
 
+            if let Some(builder) = signal_args {
+                if property_args.is_some() {
+                    return Err(syn::Error::new(
+                        field.span(),
+                        "a field cannot be both a `#[property]` and a `#[signal]`",
+                    ));
+                }
+                let mut signal = builder.done()?;
+                signal.documentation = crate::doc_comment(&field.attrs);
+                signals.push(signal);
+            }
+
             if let Some(builder) = property_args {
                 let ident = field
                     .ident
                     .clone()
                     .ok_or_else(|| syn::Error::new(field.ident.span(), "Fields should be named"))?;
-                properties.push((ident, builder.done()));
+                let mut config = builder.done();
+                config.documentation = crate::doc_comment(&field.attrs);
+                properties.push((ident, config));
             }
         }
     };
 
     Ok(DeriveData {
         name: ident,
+        documentation: crate::doc_comment(&input.attrs),
         godot_name,
         base,
         register_callback,
         user_data,
         properties,
+        signals,
+        enums,
+        mixins,
         no_constructor,
+        lifecycle_hooks,
+        super_class,
+        auto_register,
+    })
+}
+
+/// Extracts `T` from the type `Super<T>` of a `#[super_class]` field.
+fn parse_super_class_type(ty: &Type) -> Result<Type, syn::Error> {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .iter()
+            .last()
+            .filter(|seg| seg.ident == "Super")
+            .and_then(|seg| match seg.arguments {
+                syn::PathArguments::AngleBracketed(ref params) => params.args.first(),
+                _ => None,
+            })
+            .and_then(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Some(ty.clone()),
+                _ => None,
+            }),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        syn::Error::new(
+            ty.span(),
+            "`#[super_class]` fields must have the type `Super<T>`",
+        )
     })
 }
 
+fn parse_auto_register(
+    input: &DeriveInput,
+    attr: &syn::Attribute,
+) -> Result<AutoRegister, syn::Error> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            attr.span(),
+            "`#[auto_register]` does not support generic classes, use `InitHandle::add_class_as` instead",
+        ));
+    }
+
+    match attr.parse_meta()? {
+        Meta::Path(_) => Ok(AutoRegister::Class),
+        Meta::List(list) => match list.nested.iter().collect::<Vec<_>>().as_slice() {
+            [NestedMeta::Meta(Meta::Path(path))] if path.is_ident("tool") => {
+                Ok(AutoRegister::ToolClass)
+            }
+            _ => Err(syn::Error::new(
+                list.nested.span(),
+                "expected `#[auto_register]` or `#[auto_register(tool)]`",
+            )),
+        },
+        m => Err(syn::Error::new(
+            m.span(),
+            "expected `#[auto_register]` or `#[auto_register(tool)]`",
+        )),
+    }
+}
+
+/// Returns a `.with_hint(...)` call for a `hint` argument. Functions returning the hint are
+/// called, and other values are converted into it with `Into`.
+fn with_hint(hint: Expr) -> TokenStream2 {
+    quote! {
+        .with_hint({
+            #[allow(unused_imports)]
+            use ::gdnative::export::{HintFromFn as _, HintFromValue as _};
+            (&::gdnative::export::HintArg::new(#hint)).into_hint()
+        })
+    }
+}
+
+/// Generates the registration of a `#[property(nested)]` field, whose type implements
+/// `NestedProperties`.
+fn nested_property(
+    class: &Ident,
+    ident: Ident,
+    config: PropertyAttrArgs,
+    group_header: Option<TokenStream2>,
+) -> Result<TokenStream2, syn::Error> {
+    if config.default.is_some()
+        || config.hint.is_some()
+        || config.get.is_some()
+        || config.set.is_some()
+        || config.notify.is_some()
+        || config.no_editor
+    {
+        return Err(syn::Error::new(
+            ident.span(),
+            "`#[property(nested)]` can only be combined with `path` and `group`; \
+            set the other options on the fields of the nested type instead",
+        ));
+    }
+
+    let ty = &config.ty;
+    let label = config.path.unwrap_or_else(|| ident.to_string());
+    Ok(quote!({
+        #group_header
+        <#ty as ::gdnative::export::NestedProperties>::register_properties(
+            builder,
+            #label,
+            ::gdnative::export::FieldLens::new(
+                |this: &#class| &this.#ident,
+                |this: &mut #class| &mut this.#ident,
+            ),
+        );
+    }))
+}
+
+/// Parses the arguments of a `#[property]` attribute on a field of type `ty`, adding them to
+/// `property_args`.
+fn parse_property_attr(
+    attr: &syn::Attribute,
+    ty: &Type,
+    property_args: &mut Option<PropertyAttrArgsBuilder>,
+) -> Result<(), syn::Error> {
+    let meta = attr.parse_meta()?;
+
+    match meta {
+        Meta::List(MetaList { nested, .. }) => {
+            let attr_args_builder =
+                property_args.get_or_insert_with(|| PropertyAttrArgsBuilder::new(ty));
+
+            for arg in nested {
+                if let NestedMeta::Meta(Meta::NameValue(ref pair)) = arg {
+                    attr_args_builder.add_pair(pair)?;
+                } else if let NestedMeta::Meta(Meta::Path(ref path)) = arg {
+                    attr_args_builder.add_path(path)?;
+                } else {
+                    let msg = format!("Unexpected argument: {:?}", arg);
+                    return Err(syn::Error::new(arg.span(), msg));
+                }
+            }
+        }
+        Meta::Path(_) => {
+            property_args.get_or_insert_with(|| PropertyAttrArgsBuilder::new(ty));
+        }
+        m => {
+            let msg = format!("Unexpected meta variant: {:?}", m);
+            return Err(syn::Error::new(m.span(), msg));
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -337,6 +749,272 @@ mod tests {
         parse_derive_input(&input).unwrap();
     }
 
+    #[test]
+    fn derive_lifecycle_hooks() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            #[lifecycle_hooks]
+            struct Foo {}
+        };
+        let data = parse_derive_input(&input).unwrap();
+        assert!(data.lifecycle_hooks);
+
+        let tokens = derive_native_class(&input).unwrap().to_string();
+        assert!(tokens.contains("builder . lifecycle_hooks ()"));
+    }
+
+    #[test]
+    fn derive_documentation() {
+        let input = parse_quote! {
+            /// A thing.
+            #[inherit(Node)]
+            struct Foo {
+                /// Health of the thing.
+                #[property]
+                health: i64,
+                /// Emitted when hit.
+                #[signal]
+                hit: Signal<()>,
+                /// Not exported.
+                other: i64,
+            }
+        };
+        let data = parse_derive_input(&input).unwrap();
+        assert_eq!(Some("A thing."), data.documentation.as_deref());
+        assert_eq!(
+            Some("Health of the thing."),
+            data.properties[0].1.documentation.as_deref()
+        );
+        assert_eq!(
+            Some("Emitted when hit."),
+            data.signals[0].documentation.as_deref()
+        );
+
+        let tokens = derive_native_class(&input).unwrap().to_string();
+        assert!(tokens.contains(r#"builder . class_documentation ("A thing.")"#));
+        assert!(tokens.contains(r#". with_documentation ("Health of the thing.")"#));
+        assert!(tokens.contains(r#". with_documentation ("Emitted when hit.")"#));
+    }
+
+    #[test]
+    fn derive_signal_raw_ident() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                #[signal]
+                r#type: Signal<(i64,)>,
+            }
+        };
+        let data = parse_derive_input(&input).unwrap();
+        assert_eq!("type", data.signals[0].name);
+
+        let tokens = derive_native_class(&input).unwrap().to_string();
+        assert!(tokens.contains("fn emit_type"));
+        assert!(tokens.contains("fn connect_type"));
+    }
+
+    #[test]
+    fn derive_mixins() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            #[mixins(FooDamageable, inventory::FooInventory)]
+            struct Foo {}
+        };
+        let data = parse_derive_input(&input).unwrap();
+        assert_eq!(2, data.mixins.len());
+
+        let tokens = derive_native_class(&input).unwrap().to_string();
+        assert!(tokens.contains("builder . mixin :: < FooDamageable > ()"));
+        assert!(tokens.contains("builder . mixin :: < inventory :: FooInventory > ()"));
+    }
+
+    #[test]
+    fn derive_super_class() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                #[super_class]
+                bar: Super<Bar>,
+            }
+        };
+        let data = parse_derive_input(&input).unwrap();
+        let (field, super_ty) = data.super_class.unwrap();
+        assert_eq!("bar", field.to_string());
+        assert_eq!("Bar", quote!(#super_ty).to_string());
+
+        let tokens = derive_native_class(&input).unwrap().to_string();
+        assert!(tokens.contains("builder . super_class ()"));
+        assert!(tokens.contains("type Super = Bar"));
+    }
+
+    #[test]
+    fn derive_super_class_requires_super_type() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                #[super_class]
+                bar: Bar,
+            }
+        };
+        assert!(parse_derive_input(&input).is_err());
+    }
+
+    #[test]
+    fn derive_property_group() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                #[property(group = "Movement")]
+                speed: f32,
+                #[property(group = "Movement")]
+                acceleration: f32,
+                #[property]
+                health: i64,
+            }
+        };
+        let data = parse_derive_input(&input).unwrap();
+        assert_eq!(Some("Movement"), data.properties[1].1.group.as_deref());
+        assert_eq!(None, data.properties[2].1.group);
+
+        let tokens = derive_native_class(&input).unwrap().to_string();
+        assert_eq!(1, tokens.matches("property_group (\"Movement\"").count());
+        assert_eq!(1, tokens.matches("property_group (\"\"").count());
+    }
+
+    #[test]
+    fn derive_property_group_reused() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                #[property(group = "Movement")]
+                speed: f32,
+                #[property(group = "Combat")]
+                health: i64,
+                #[property(group = "Movement")]
+                acceleration: f32,
+            }
+        };
+        assert!(derive_native_class(&input).is_err());
+    }
+
+    #[test]
+    fn derive_property_group_after_ungrouped() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                #[property(group = "Movement")]
+                speed: f32,
+                #[property]
+                name: String,
+                #[property(group = "Combat")]
+                health: i64,
+            }
+        };
+        assert!(derive_native_class(&input).is_err());
+    }
+
+    #[test]
+    fn derive_property_notify() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                #[property(notify = "health_changed")]
+                health: i64,
+            }
+        };
+        let data = parse_derive_input(&input).unwrap();
+        assert_eq!(
+            Some("health_changed"),
+            data.properties[0].1.notify.as_deref()
+        );
+
+        let tokens = derive_native_class(&input).unwrap().to_string();
+        assert!(tokens.contains("with_notify (\"health_changed\")"));
+    }
+
+    #[test]
+    fn derive_property_hint_expr() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                #[property(hint = "Self::speed_hint")]
+                speed: f64,
+                #[property(hint = "IntHint::Layers2DPhysics")]
+                layers: i64,
+                #[property(hint = "NodePathHint::valid_types::<Node2D>()")]
+                target: NodePath,
+            }
+        };
+        let tokens = derive_native_class(&input).unwrap().to_string();
+        assert!(tokens.contains("HintArg :: new (Self :: speed_hint)"));
+        assert!(tokens.contains("HintArg :: new (IntHint :: Layers2DPhysics)"));
+        assert!(tokens.contains("HintArg :: new (NodePathHint :: valid_types :: < Node2D > ())"));
+    }
+
+    #[test]
+    fn derive_nested_properties() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                #[property(nested, path = "settings")]
+                config: Config,
+            }
+        };
+        let tokens = derive_native_class(&input).unwrap().to_string();
+        assert!(tokens.contains(
+            "< Config as :: gdnative :: export :: NestedProperties > :: register_properties (builder , \"settings\""
+        ));
+
+        let input = parse_quote! {
+            struct Config {
+                #[property(default = 1.0)]
+                speed: f64,
+                #[property(nested)]
+                inner: Inner,
+                skipped: i64,
+            }
+        };
+        let tokens = super::derive_nested_properties(&input).unwrap().to_string();
+        assert!(tokens.contains("builder . property :: < f64 >"));
+        assert!(tokens.contains("< Inner as :: gdnative :: export :: NestedProperties >"));
+        assert!(!tokens.contains("skipped"));
+        assert!(syn::parse2::<syn::File>(tokens.parse().unwrap()).is_ok());
+
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                #[property(nested, default = 1)]
+                config: Config,
+            }
+        };
+        assert!(derive_native_class(&input).is_err());
+    }
+
+    #[test]
+    fn derive_auto_register() {
+        let input = parse_quote! {
+            #[auto_register]
+            struct Foo;
+        };
+        let data = parse_derive_input(&input).unwrap();
+        assert_eq!(Some(AutoRegister::Class), data.auto_register);
+
+        let input = parse_quote! {
+            #[auto_register(tool)]
+            struct Foo;
+        };
+        let tokens = derive_native_class(&input).unwrap().to_string();
+        assert!(tokens.contains("godot_auto_register_class ! (Foo , add_tool_class)"));
+
+        let input = parse_quote! {
+            #[auto_register]
+            struct Foo<T> {
+                value: T,
+            }
+        };
+        assert!(parse_derive_input(&input).is_err());
+    }
+
     #[test]
     fn derive_property_get_set() {
         let input = parse_quote! {
@@ -373,6 +1051,66 @@ mod tests {
         parse_derive_input(&input).unwrap();
     }
 
+    #[test]
+    fn derive_signal() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            struct Foo {
+                #[signal(params = "amount, position")]
+                bar: Signal<(i64, Vector2)>,
+                #[signal(name = "renamed")]
+                baz: gdnative::export::Signal<()>,
+            }
+        };
+        let data = parse_derive_input(&input).unwrap();
+        assert_eq!(2, data.signals.len());
+        assert_eq!("bar", data.signals[0].name);
+        assert_eq!(2, data.signals[0].params.len());
+        assert_eq!(data.signals[0].params[1].0, "position");
+        assert_eq!("renamed", data.signals[1].name);
+        assert!(data.signals[1].params.is_empty());
+    }
+
+    #[test]
+    fn derive_export_enums() {
+        let input = parse_quote! {
+            #[inherit(Node)]
+            #[export_enums(State, foo::Mode)]
+            struct Foo {}
+        };
+        let data = parse_derive_input(&input).unwrap();
+        assert_eq!(2, data.enums.len());
+        assert!(data.enums[0].is_ident("State"));
+    }
+
+    #[test]
+    fn derive_signal_invalid() {
+        let wrong_type: DeriveInput = parse_quote! {
+            struct Foo {
+                #[signal]
+                bar: i64,
+            }
+        };
+        assert!(parse_derive_input(&wrong_type).is_err());
+
+        let wrong_param_count: DeriveInput = parse_quote! {
+            struct Foo {
+                #[signal(params = "amount")]
+                bar: Signal<(i64, Vector2)>,
+            }
+        };
+        assert!(parse_derive_input(&wrong_param_count).is_err());
+
+        let also_property: DeriveInput = parse_quote! {
+            struct Foo {
+                #[signal]
+                #[property]
+                bar: Signal<()>,
+            }
+        };
+        assert!(parse_derive_input(&also_property).is_err());
+    }
+
     #[test]
     fn derive_property_combinations() {
         let attr_none = quote! {       #[property]                          };
@@ -0,0 +1,130 @@
+use proc_macro2::TokenStream as TokenStream2;
+
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Fields};
+
+use super::property_args::PropertyAttrArgs;
+
+pub(crate) fn derive_nested_properties(input: &DeriveInput) -> Result<TokenStream2, syn::Error> {
+    let derived = crate::automatically_derived();
+    let name = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "`NestedProperties` derive macro does not support generic types",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            fields => {
+                return Err(syn::Error::new(
+                    fields.span(),
+                    "`NestedProperties` derive macro only works on structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "`NestedProperties` derive macro only works on structs",
+            ))
+        }
+    };
+
+    let mut properties = Vec::new();
+    for field in fields {
+        let mut property_args = None;
+        for attr in field.attrs.iter() {
+            if attr.path.is_ident("property") {
+                super::parse_property_attr(attr, &field.ty, &mut property_args)?;
+            }
+        }
+
+        if let Some(builder) = property_args {
+            let ident = field.ident.as_ref().expect("fields should be named");
+            properties.push(nested_field(ident, builder.done())?);
+        }
+    }
+
+    Ok(quote! {
+        #derived
+        impl ::gdnative::export::NestedProperties for #name {
+            fn register_properties<C, L>(
+                builder: &::gdnative::export::ClassBuilder<C>,
+                prefix: &str,
+                lens: L,
+            ) where
+                C: ::gdnative::export::NativeClass,
+                C::UserData: ::gdnative::export::user_data::Map
+                    + ::gdnative::export::user_data::MapMut,
+                L: ::gdnative::export::PropertyLens<C, Self>,
+            {
+                #(#properties)*
+            }
+        }
+    })
+}
+
+/// Generates the registration of a field of a `NestedProperties` type.
+fn nested_field(ident: &syn::Ident, config: PropertyAttrArgs) -> Result<TokenStream2, syn::Error> {
+    if config.get.is_some()
+        || config.set.is_some()
+        || config.notify.is_some()
+        || config.group.is_some()
+    {
+        return Err(syn::Error::new(
+            ident.span(),
+            "`get`, `set`, `notify` and `group` are not supported on fields of `NestedProperties` types",
+        ));
+    }
+
+    let ty = &config.ty;
+    let label = config.path.unwrap_or_else(|| ident.to_string());
+    let path = quote!(&::std::format!("{}/{}", prefix, #label));
+
+    if config.nested {
+        if config.default.is_some() || config.hint.is_some() || config.no_editor {
+            return Err(syn::Error::new(
+                ident.span(),
+                "`#[property(nested)]` can only be combined with `path`",
+            ));
+        }
+
+        return Ok(quote! {
+            <#ty as ::gdnative::export::NestedProperties>::register_properties(
+                builder,
+                #path,
+                ::gdnative::export::PropertyLens::field(
+                    lens,
+                    |this: &Self| &this.#ident,
+                    |this: &mut Self| &mut this.#ident,
+                ),
+            );
+        });
+    }
+
+    let with_default = config
+        .default
+        .map(|default_value| quote!(.with_default(#default_value)));
+    let with_hint = config.hint.map(super::with_hint);
+    let with_usage = config
+        .no_editor
+        .then(|| quote!(.with_usage(::gdnative::export::PropertyUsage::NOEDITOR)));
+
+    Ok(quote! {
+        builder.property::<#ty>(#path)
+            #with_default
+            #with_hint
+            #with_usage
+            .with_ref_getter(move |this: &C, _owner: ::gdnative::object::TRef<C::Base>| {
+                &::gdnative::export::PropertyLens::get(&lens, this).#ident
+            })
+            .with_setter(move |this: &mut C, _owner: ::gdnative::object::TRef<C::Base>, v| {
+                ::gdnative::export::PropertyLens::get_mut(&lens, this).#ident = v;
+            })
+            .done();
+    })
+}
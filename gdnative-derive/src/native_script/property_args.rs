@@ -19,20 +19,27 @@ pub struct PropertyAttrArgs {
     pub ty: syn::Type,
     pub path: Option<String>,
     pub default: Option<syn::Lit>,
-    pub hint: Option<syn::Path>,
+    pub hint: Option<syn::Expr>,
     pub get: Option<PropertyGet>,
     pub set: Option<PropertySet>,
     pub no_editor: bool,
+    pub nested: bool,
+    pub group: Option<String>,
+    pub notify: Option<String>,
+    pub documentation: Option<String>,
 }
 
 pub struct PropertyAttrArgsBuilder {
     ty: syn::Type,
     path: Option<String>,
     default: Option<syn::Lit>,
-    hint: Option<syn::Path>,
+    hint: Option<syn::Expr>,
     get: Option<PropertyGet>,
     set: Option<PropertySet>,
     no_editor: bool,
+    nested: bool,
+    group: Option<String>,
+    notify: Option<String>,
 }
 
 impl PropertyAttrArgsBuilder {
@@ -45,6 +52,9 @@ impl PropertyAttrArgsBuilder {
             get: None,
             set: None,
             no_editor: false,
+            nested: false,
+            group: None,
+            notify: None,
         }
     }
 
@@ -124,7 +134,28 @@ impl PropertyAttrArgsBuilder {
                     .ok_or_else(|| Self::err_attr_not_a_string_literal(pair.span(), "path"))?;
                 update_prop!(path, path.value());
             }
-            "hint" => process_path_input!(hint),
+            "group" => {
+                let group = Self::extract_lit_str(&pair.lit)
+                    .ok_or_else(|| Self::err_attr_not_a_string_literal(pair.span(), "group"))?;
+                update_prop!(group, group.value());
+            }
+            "notify" => {
+                let notify = Self::extract_lit_str(&pair.lit)
+                    .ok_or_else(|| Self::err_attr_not_a_string_literal(pair.span(), "notify"))?;
+                update_prop!(notify, notify.value());
+            }
+            "hint" => {
+                let expr = Self::extract_lit_str(&pair.lit)
+                    .ok_or_else(|| Self::err_attr_not_a_string_literal(pair.span(), "hint"))?
+                    .parse::<syn::Expr>()
+                    .map_err(|_| {
+                        syn::Error::new(
+                            pair.lit.span(),
+                            "Unexpected input, expected a double quoted path or expression: \"path::to::hint_fn\"",
+                        )
+                    })?;
+                update_prop!(hint, expr);
+            }
             "get" => process_path_input!(get, PropertyGet::Owned),
             "get_ref" => process_path_input!(get, PropertyGet::Ref),
             "set" => process_path_input!(set, PropertySet::WithPath),
@@ -142,6 +173,8 @@ impl PropertyAttrArgsBuilder {
     pub fn add_path(&mut self, path: &syn::Path) -> Result<(), syn::Error> {
         if path.is_ident("no_editor") {
             self.no_editor = true;
+        } else if path.is_ident("nested") {
+            self.nested = true;
         } else if path.is_ident("get") {
             if let Some(get) = self.get.replace(PropertyGet::Default) {
                 return Err(Self::err_prop_already_set(path.span(), "get", &get));
@@ -171,6 +204,10 @@ impl PropertyAttrArgsBuilder {
             get: self.get,
             set: self.set,
             no_editor: self.no_editor,
+            nested: self.nested,
+            group: self.group,
+            notify: self.notify,
+            documentation: None,
         }
     }
 }
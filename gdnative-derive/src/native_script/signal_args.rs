@@ -0,0 +1,146 @@
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+
+/// A signal declared through a `#[signal]` field of type `Signal<Args>`.
+pub struct SignalDecl {
+    pub ident: syn::Ident,
+    pub vis: syn::Visibility,
+    pub name: String,
+    pub params: Vec<(syn::Ident, syn::Type)>,
+    pub documentation: Option<String>,
+}
+
+pub struct SignalAttrArgsBuilder {
+    ident: syn::Ident,
+    vis: syn::Visibility,
+    ty: syn::Type,
+    name: Option<String>,
+    param_names: Option<Vec<syn::Ident>>,
+}
+
+impl SignalAttrArgsBuilder {
+    pub fn new(ident: &syn::Ident, vis: &syn::Visibility, ty: &syn::Type) -> Self {
+        Self {
+            ident: ident.clone(),
+            vis: vis.clone(),
+            ty: ty.clone(),
+            name: None,
+            param_names: None,
+        }
+    }
+
+    pub fn add_pair(&mut self, pair: &syn::MetaNameValue) -> Result<(), syn::Error> {
+        let lit_str = if let syn::Lit::Str(lit_str) = &pair.lit {
+            lit_str
+        } else {
+            let name = pair.path.to_token_stream().to_string();
+            return Err(syn::Error::new(
+                pair.lit.span(),
+                format!("'{}' value is not a string literal", name),
+            ));
+        };
+
+        if pair.path.is_ident("name") {
+            if self.name.replace(lit_str.value()).is_some() {
+                return Err(syn::Error::new(
+                    pair.span(),
+                    "`name` was set more than once",
+                ));
+            }
+        } else if pair.path.is_ident("params") {
+            let names = lit_str
+                .value()
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| {
+                    syn::parse_str::<syn::Ident>(name).map_err(|_| {
+                        syn::Error::new(
+                            lit_str.span(),
+                            format!("signal parameter name `{}` is not a valid identifier", name),
+                        )
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if self.param_names.replace(names).is_some() {
+                return Err(syn::Error::new(
+                    pair.span(),
+                    "`params` was set more than once",
+                ));
+            }
+        } else {
+            return Err(syn::Error::new(
+                pair.span(),
+                format!("unexpected argument: {}", pair.path.to_token_stream()),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn done(self) -> Result<SignalDecl, syn::Error> {
+        let param_types = signal_param_types(&self.ty).ok_or_else(|| {
+            syn::Error::new(
+                self.ty.span(),
+                "`#[signal]` fields must have the type `Signal<Args>`, where `Args` is a tuple of parameter types",
+            )
+        })?;
+
+        let param_names = match self.param_names {
+            Some(names) => {
+                if names.len() != param_types.len() {
+                    return Err(syn::Error::new(
+                        self.ident.span(),
+                        format!(
+                            "signal has {} parameter types, but {} parameter names were given",
+                            param_types.len(),
+                            names.len(),
+                        ),
+                    ));
+                }
+                names
+            }
+            None => (0..param_types.len())
+                .map(|i| syn::Ident::new(&format!("arg{}", i), Span::call_site()))
+                .collect(),
+        };
+
+        Ok(SignalDecl {
+            name: self.name.unwrap_or_else(|| self.ident.unraw().to_string()),
+            ident: self.ident,
+            vis: self.vis,
+            params: param_names.into_iter().zip(param_types).collect(),
+            documentation: None,
+        })
+    }
+}
+
+/// Extracts the parameter types `A, B, ...` from a type `Signal<(A, B, ...)>`. A non-tuple
+/// argument `Signal<A>` is treated as a signal with a single parameter.
+fn signal_param_types(ty: &syn::Type) -> Option<Vec<syn::Type>> {
+    let segment = match ty {
+        syn::Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+
+    if segment.ident != "Signal" {
+        return None;
+    }
+
+    let args = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => &args.args,
+        _ => return None,
+    };
+
+    match args.first()? {
+        syn::GenericArgument::Type(syn::Type::Tuple(tuple)) => {
+            Some(tuple.elems.iter().cloned().collect())
+        }
+        syn::GenericArgument::Type(syn::Type::Paren(paren)) => Some(vec![(*paren.elem).clone()]),
+        syn::GenericArgument::Type(ty) => Some(vec![ty.clone()]),
+        _ => None,
+    }
+}
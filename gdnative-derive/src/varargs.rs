@@ -48,16 +48,18 @@ pub(crate) fn derive_from_varargs(input: DeriveInput) -> Result<TokenStream2, sy
 
         let mut required = Vec::new();
         let mut optional = Vec::new();
+        let mut opt_defaults = Vec::new();
         for field in fields {
-            let is_optional = field.attrs.iter().any(|attr| attr.path.is_ident("opt"));
-            if !is_optional && !optional.is_empty() {
+            let opt_attr = field.attrs.iter().find(|attr| attr.path.is_ident("opt"));
+            if opt_attr.is_none() && !optional.is_empty() {
                 return Err(syn::Error::new(
                     field.ident.span(),
                     "cannot add required arguments after optional ones",
                 ));
             }
-            if is_optional {
+            if let Some(attr) = opt_attr {
                 optional.push(field);
+                opt_defaults.push(parse_opt_default(attr)?);
             } else {
                 required.push(field);
             }
@@ -110,6 +112,13 @@ pub(crate) fn derive_from_varargs(input: DeriveInput) -> Result<TokenStream2, sy
             .iter()
             .map(|field| format!("{}", field.ty.to_token_stream()))
             .collect::<Vec<_>>();
+        let opt_var_defaults = opt_defaults
+            .into_iter()
+            .map(|default| match default {
+                Some(expr) => quote!(.unwrap_or_else(|| #expr)),
+                None => quote!(.unwrap_or_default()),
+            })
+            .collect::<Vec<_>>();
 
         Ok(quote! {
             #derived
@@ -136,7 +145,7 @@ pub(crate) fn derive_from_varargs(input: DeriveInput) -> Result<TokenStream2, sy
                             .map_err(|err| __errors.push(err))
                             .ok()
                             .flatten()
-                            .unwrap_or_default();
+                            #opt_var_defaults;
                     )*
 
                     if !__errors.is_empty() {
@@ -161,3 +170,23 @@ pub(crate) fn derive_from_varargs(input: DeriveInput) -> Result<TokenStream2, sy
         ))
     }
 }
+
+/// Parses an `#[opt]` or `#[opt(default = expr)]` attribute, returning the default expression
+/// if there is one.
+pub(crate) fn parse_opt_default(attr: &syn::Attribute) -> Result<Option<syn::Expr>, syn::Error> {
+    if attr.tokens.is_empty() {
+        return Ok(None);
+    }
+
+    attr.parse_args_with(|input: syn::parse::ParseStream| {
+        let key = input.parse::<Ident>()?;
+        if key != "default" {
+            return Err(syn::Error::new(
+                key.span(),
+                format!("unexpected argument: {}, expected `default = <expr>`", key),
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        input.parse::<syn::Expr>().map(Some)
+    })
+}
@@ -3,6 +3,7 @@ use std::iter::FromIterator;
 use proc_macro2::Span;
 use syn::spanned::Spanned;
 
+use super::rename::RenameRule;
 use super::Direction;
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -11,6 +12,19 @@ pub struct Attr {
     pub skip_from_variant: bool,
     pub to_variant_with: Option<syn::Path>,
     pub from_variant_with: Option<syn::Path>,
+    pub rename: Option<String>,
+    pub default: Option<FieldDefault>,
+    pub flatten: bool,
+}
+
+/// Source of the value of a field missing from a `Dictionary`, or skipped when converting from
+/// `Variant`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum FieldDefault {
+    /// `#[variant(default)]`
+    Default,
+    /// `#[variant(default = "path::to::func")]`
+    Path(syn::Path),
 }
 
 impl Attr {
@@ -28,6 +42,9 @@ pub struct AttrBuilder {
     skip_from_variant: bool,
     to_variant_with: Option<syn::Path>,
     from_variant_with: Option<syn::Path>,
+    rename: Option<String>,
+    default: Option<FieldDefault>,
+    flatten: bool,
     errors: Vec<syn::Error>,
 }
 
@@ -93,16 +110,25 @@ impl AttrBuilder {
             match name.as_str() {
                 skip_to_variant,
                 skip_from_variant,
+                flatten,
             }
         }
 
-        #[allow(clippy::single_match)]
         match name.as_str() {
             "skip" => {
                 self.skip_to_variant = true;
                 self.skip_from_variant = true;
                 return Ok(());
             }
+            "default" => {
+                if self.default.replace(FieldDefault::Default).is_some() {
+                    return Err(syn::Error::new(
+                        flag.span(),
+                        "the argument default is already set",
+                    ));
+                }
+                return Ok(());
+            }
             _ => {}
         }
 
@@ -122,7 +148,7 @@ impl AttrBuilder {
         let syn::MetaNameValue { path, lit, .. } = pair;
 
         const VALID_KEYS: &str =
-            "to_variant_with, from_variant_with, with, skip_to_variant, skip_from_variant, skip";
+            "to_variant_with, from_variant_with, with, skip_to_variant, skip_from_variant, skip, rename, default, flatten";
 
         let name = path
             .get_ident()
@@ -216,6 +242,46 @@ impl AttrBuilder {
 
                 return Ok(());
             }
+            "default" => {
+                let path = match lit {
+                    syn::Lit::Str(lit_str) => lit_str.parse::<syn::Path>()?,
+                    _ => {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "expecting a path to a function in double quotes: #[variant(default = \"path::to::func\")]",
+                        ))
+                    }
+                };
+
+                if self.default.replace(FieldDefault::Path(path)).is_some() {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "the argument default is already set",
+                    ));
+                }
+
+                return Ok(());
+            }
+            "rename" => {
+                let name = match lit {
+                    syn::Lit::Str(lit_str) => lit_str.value(),
+                    _ => {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "expecting a name in double quotes: #[variant(rename = \"name\")]",
+                        ))
+                    }
+                };
+
+                if self.rename.replace(name).is_some() {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "the argument rename is already set",
+                    ));
+                }
+
+                return Ok(());
+            }
             _ => {}
         }
 
@@ -247,6 +313,9 @@ impl AttrBuilder {
                 skip_from_variant: self.skip_from_variant,
                 to_variant_with: self.to_variant_with,
                 from_variant_with: self.from_variant_with,
+                rename: self.rename,
+                default: self.default,
+                flatten: self.flatten,
             })
         } else {
             let first_error = self.errors.remove(0);
@@ -262,3 +331,98 @@ impl AttrBuilder {
         }
     }
 }
+
+/// Arguments of `#[variant(...)]` attributes on the type itself.
+#[derive(Debug, Default)]
+pub(crate) struct ContainerAttr {
+    pub repr: Option<syn::LitStr>,
+    pub tag: Option<syn::LitStr>,
+    pub rename_all: Option<RenameRule>,
+    pub deny_unknown_fields: bool,
+    pub collect_errors: bool,
+}
+
+impl ContainerAttr {
+    pub(crate) fn parse<'a, I>(attrs: I) -> Result<Self, syn::Error>
+    where
+        I: IntoIterator<Item = &'a syn::Attribute>,
+    {
+        const VALID_KEYS: &str = "repr, tag, rename_all, deny_unknown_fields, collect_errors";
+
+        let mut container = ContainerAttr::default();
+
+        for attr in attrs
+            .into_iter()
+            .filter(|attr| attr.path.is_ident("variant"))
+        {
+            let list = match attr.parse_meta()? {
+                syn::Meta::List(list) => list,
+                meta => {
+                    return Err(syn::Error::new(
+                        meta.span(),
+                        "expecting a list of arguments: #[variant(...)]",
+                    ))
+                }
+            };
+
+            for nested in list.nested.iter() {
+                if let syn::NestedMeta::Meta(syn::Meta::Path(path)) = nested {
+                    if path.is_ident("deny_unknown_fields") {
+                        container.deny_unknown_fields = true;
+                        continue;
+                    }
+                    if path.is_ident("collect_errors") {
+                        container.collect_errors = true;
+                        continue;
+                    }
+                }
+
+                let (name, value) = match nested {
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                        path,
+                        lit: syn::Lit::Str(value),
+                        ..
+                    })) if path.get_ident().is_some() => (path.get_ident().unwrap(), value),
+                    _ => {
+                        return Err(syn::Error::new(
+                            nested.span(),
+                            format!("unknown argument, expected one of:\n\t{}", VALID_KEYS),
+                        ))
+                    }
+                };
+
+                let already_set = match name.to_string().as_str() {
+                    "repr" => container.repr.replace(value.clone()).is_some(),
+                    "tag" => container.tag.replace(value.clone()).is_some(),
+                    "rename_all" => {
+                        let rule = RenameRule::from_name(&value.value()).ok_or_else(|| {
+                            syn::Error::new(
+                                value.span(),
+                                format!(
+                                    "unknown case convention, expected one of:\n\t{}",
+                                    RenameRule::VALID_NAMES
+                                ),
+                            )
+                        })?;
+                        container.rename_all.replace(rule).is_some()
+                    }
+                    _ => {
+                        return Err(syn::Error::new(
+                            name.span(),
+                            format!("unknown argument, expected one of:\n\t{}", VALID_KEYS),
+                        ))
+                    }
+                };
+
+                if already_set {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        format!("the argument {} is already set", name),
+                    ));
+                }
+            }
+        }
+
+        Ok(container)
+    }
+}
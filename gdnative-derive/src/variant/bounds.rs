@@ -39,12 +39,12 @@ pub(crate) fn extend_bounds(
         }
 
         match repr {
-            Repr::Enum(ref variants) => {
-                for (_, var_repr) in variants.iter() {
-                    visit_var_repr(visitor, var_repr, dir);
+            Repr::Enum(_, ref variants) => {
+                for variant in variants.iter() {
+                    visit_var_repr(visitor, &variant.repr, dir);
                 }
             }
-            Repr::Struct(var_repr) => {
+            Repr::Struct(_, var_repr) => {
                 visit_var_repr(visitor, var_repr, dir);
             }
         }
@@ -2,7 +2,7 @@ use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
 
 use syn::Ident;
 
-use super::repr::Repr;
+use super::repr::{EnumRepr, Repr, StructRepr, VariantRepr};
 use super::DeriveData;
 
 pub(crate) fn expand_from_variant(derive_data: DeriveData) -> Result<TokenStream2, syn::Error> {
@@ -10,6 +10,7 @@ pub(crate) fn expand_from_variant(derive_data: DeriveData) -> Result<TokenStream
         ident,
         repr,
         mut generics,
+        from_options,
     } = derive_data;
 
     let derived = crate::automatically_derived();
@@ -21,15 +22,24 @@ pub(crate) fn expand_from_variant(derive_data: DeriveData) -> Result<TokenStream
     let input_ident = Ident::new("__variant", Span::call_site());
 
     let return_expr = match repr {
-        Repr::Struct(var_repr) => {
-            let from_variant = var_repr.make_from_variant_expr(&input_ident, &quote! { #ident })?;
+        Repr::Struct(struct_repr, var_repr) => {
+            let from_variant = match struct_repr {
+                StructRepr::Dictionary => var_repr.make_from_variant_expr(
+                    &input_ident,
+                    &quote! { #ident },
+                    from_options,
+                )?,
+                StructRepr::Array => {
+                    var_repr.make_from_array_expr(&input_ident, &quote! { #ident }, from_options)
+                }
+            };
             quote! {
                 {
                     #from_variant
                 }
             }
         }
-        Repr::Enum(variants) => {
+        Repr::Enum(enum_repr, variants) => {
             if variants.is_empty() {
                 return Err(syn::Error::new(
                     ident.span(),
@@ -37,68 +47,187 @@ pub(crate) fn expand_from_variant(derive_data: DeriveData) -> Result<TokenStream
                 ));
             }
 
-            let var_input_ident = Ident::new("__enum_variant", Span::call_site());
-
-            let var_ident_strings: Vec<String> = variants
-                .iter()
-                .map(|(var_ident, _)| format!("{}", var_ident))
-                .collect();
-
-            let var_ident_string_literals = var_ident_strings
+            let var_name_literals = variants
                 .iter()
-                .map(|string| Literal::string(string))
+                .map(|variant| Literal::string(&variant.name))
                 .collect::<Vec<_>>();
 
-            let ref_var_ident_string_literals = &var_ident_string_literals;
+            let ref_var_name_literals = &var_name_literals;
 
-            let var_from_variants = variants
-                .iter()
-                .map(|(var_ident, var_repr)| {
-                    var_repr
-                        .make_from_variant_expr(&var_input_ident, &quote! { #ident::#var_ident })
-                })
-                .collect::<Result<Vec<_>, _>>()?;
+            match enum_repr {
+                EnumRepr::ExternallyTagged => {
+                    let var_input_ident = Ident::new("__enum_variant", Span::call_site());
 
-            let var_input_ident_iter = std::iter::repeat(&var_input_ident);
+                    let var_from_variants = variants
+                        .iter()
+                        .map(|variant| {
+                            let var_ident = &variant.ident;
+                            variant.repr.make_from_variant_expr(
+                                &var_input_ident,
+                                &quote! { #ident::#var_ident },
+                                from_options,
+                            )
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
 
-            quote! {
-                {
-                    let __dict = ::gdnative::core_types::Dictionary::from_variant(#input_ident)
-                        .map_err(|__err| FVE::InvalidEnumRepr {
-                            expected: VariantEnumRepr::ExternallyTagged,
-                            error: std::boxed::Box::new(__err),
-                        })?;
-
-                    let __keys = __dict.keys();
-                    if __keys.len() != 1 {
-                        Err(FVE::InvalidEnumRepr {
-                            expected: VariantEnumRepr::ExternallyTagged,
-                            error: std::boxed::Box::new(FVE::InvalidLength {
-                                expected: 1,
-                                len: __keys.len() as usize,
-                            }),
+                    let var_input_ident_iter = std::iter::repeat(&var_input_ident);
+
+                    quote! {
+                        {
+                            let __dict = ::gdnative::core_types::Dictionary::from_variant(#input_ident)
+                                .map_err(|__err| FVE::InvalidEnumRepr {
+                                    expected: VariantEnumRepr::ExternallyTagged,
+                                    error: std::boxed::Box::new(__err),
+                                })?;
+
+                            let __keys = __dict.keys();
+                            if __keys.len() != 1 {
+                                Err(FVE::InvalidEnumRepr {
+                                    expected: VariantEnumRepr::ExternallyTagged,
+                                    error: std::boxed::Box::new(FVE::InvalidLength {
+                                        expected: 1,
+                                        len: __keys.len() as usize,
+                                    }),
+                                })
+                            }
+                            else {
+                                let __key = String::from_variant(&__keys.get(0))
+                                    .map_err(|__err| FVE::InvalidEnumRepr {
+                                        expected: VariantEnumRepr::ExternallyTagged,
+                                        error: std::boxed::Box::new(__err),
+                                    })?;
+                                match __key.as_str() {
+                                    #(
+                                        #ref_var_name_literals => {
+                                            let #var_input_ident_iter = &__dict.get_or_nil(&__keys.get(0));
+                                            (#var_from_variants).map_err(|err| FVE::InvalidEnumVariant {
+                                                variant: #ref_var_name_literals,
+                                                error: std::boxed::Box::new(err),
+                                            })
+                                        },
+                                    )*
+                                    variant => Err(FVE::UnknownEnumVariant {
+                                        variant: variant.to_string(),
+                                        expected: &[#(#ref_var_name_literals),*],
+                                    }),
+                                }
+                            }
+                        }
+                    }
+                }
+                EnumRepr::InternallyTagged(tag) => {
+                    let tag_literal = Literal::string(&tag);
+
+                    let var_from_dicts = variants.iter().map(|variant| {
+                        let var_ident = &variant.ident;
+                        let from_dict = variant.repr.make_from_dict_body(
+                            &quote! { #ident::#var_ident },
+                            from_options,
+                            Some(&tag),
+                        );
+                        quote! {
+                            std::result::Result::Ok(&__dict).and_then(|__dict| {
+                                #from_dict
+                            })
+                        }
+                    });
+
+                    quote! {
+                        {
+                            let __dict = ::gdnative::core_types::Dictionary::from_variant(#input_ident)
+                                .map_err(|__err| FVE::InvalidEnumRepr {
+                                    expected: VariantEnumRepr::InternallyTagged,
+                                    error: std::boxed::Box::new(__err),
+                                })?;
+
+                            let __tag_name = #tag_literal;
+                            let __tag = String::from_variant(&__dict.get_or_nil(__tag_name))
+                                .map_err(|__err| FVE::InvalidEnumRepr {
+                                    expected: VariantEnumRepr::InternallyTagged,
+                                    error: std::boxed::Box::new(FVE::InvalidField {
+                                        field_name: __tag_name,
+                                        error: std::boxed::Box::new(__err),
+                                    }),
+                                })?;
+
+                            match __tag.as_str() {
+                                #(
+                                    #ref_var_name_literals => {
+                                        (#var_from_dicts).map_err(|err| FVE::InvalidEnumVariant {
+                                            variant: #ref_var_name_literals,
+                                            error: std::boxed::Box::new(err),
+                                        })
+                                    },
+                                )*
+                                variant => Err(FVE::UnknownEnumVariant {
+                                    variant: variant.to_string(),
+                                    expected: &[#(#ref_var_name_literals),*],
+                                }),
+                            }
+                        }
+                    }
+                }
+                EnumRepr::Untagged => {
+                    let var_attempts = variants
+                        .iter()
+                        .map(|variant| {
+                            let var_ident = &variant.ident;
+                            let ctor = quote! { #ident::#var_ident };
+                            let tokens = match &variant.repr {
+                                VariantRepr::Unit => quote! {
+                                    if #input_ident.is_nil() {
+                                        return Ok(#ctor);
+                                    }
+                                },
+                                var_repr => {
+                                    let from_variant = var_repr.make_from_variant_expr(
+                                        &input_ident,
+                                        &ctor,
+                                        from_options,
+                                    )?;
+                                    quote! {
+                                        if let Ok(__value) = (#from_variant) {
+                                            return Ok(__value);
+                                        }
+                                    }
+                                }
+                            };
+                            Ok(tokens)
                         })
+                        .collect::<Result<Vec<_>, syn::Error>>()?;
+
+                    quote! {
+                        {
+                            #( #var_attempts )*
+                            Err(FVE::InvalidEnumRepr {
+                                expected: VariantEnumRepr::Untagged,
+                                error: std::boxed::Box::new(FVE::custom(
+                                    "value does not match any variant",
+                                )),
+                            })
+                        }
                     }
-                    else {
-                        let __key = String::from_variant(&__keys.get(0))
-                            .map_err(|__err| FVE::InvalidEnumRepr {
-                                expected: VariantEnumRepr::ExternallyTagged,
-                                error: std::boxed::Box::new(__err),
-                            })?;
-                        match __key.as_str() {
+                }
+                EnumRepr::Int => {
+                    let var_idents = variants.iter().map(|variant| &variant.ident);
+                    let var_idents_ctor = variants.iter().map(|variant| &variant.ident);
+
+                    quote! {
+                        {
+                            let __value = i64::from_variant(#input_ident)
+                                .map_err(|__err| FVE::InvalidEnumRepr {
+                                    expected: VariantEnumRepr::Int,
+                                    error: std::boxed::Box::new(__err),
+                                })?;
                             #(
-                                #ref_var_ident_string_literals => {
-                                    let #var_input_ident_iter = &__dict.get_or_nil(&__keys.get(0));
-                                    (#var_from_variants).map_err(|err| FVE::InvalidEnumVariant {
-                                        variant: #ref_var_ident_string_literals,
-                                        error: std::boxed::Box::new(err),
-                                    })
-                                },
+                                if __value == #ident::#var_idents as i64 {
+                                    return Ok(#ident::#var_idents_ctor);
+                                }
                             )*
-                            variant => Err(FVE::UnknownEnumVariant {
-                                variant: variant.to_string(),
-                                expected: &[#(#ref_var_ident_string_literals),*],
-                            }),
+                            Err(FVE::UnknownEnumVariant {
+                                variant: __value.to_string(),
+                                expected: &[#(#ref_var_name_literals),*],
+                            })
                         }
                     }
                 }
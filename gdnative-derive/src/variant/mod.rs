@@ -4,16 +4,19 @@ use syn::{spanned::Spanned, Data, DeriveInput, Generics, Ident};
 mod attr;
 mod bounds;
 mod from;
+mod rename;
 mod repr;
 mod to;
 
+use attr::ContainerAttr;
 use bounds::extend_bounds;
-use repr::{Repr, VariantRepr};
+use repr::{EnumRepr, EnumVariant, FromVariantOptions, Repr, StructRepr, VariantRepr};
 
 pub(crate) struct DeriveData {
     pub(crate) ident: Ident,
     pub(crate) repr: Repr,
     pub(crate) generics: Generics,
+    pub(crate) from_options: FromVariantOptions,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -56,20 +59,42 @@ pub(crate) fn parse_derive_input(
     bound: &syn::Path,
     dir: Direction,
 ) -> Result<DeriveData, syn::Error> {
+    let container = ContainerAttr::parse(&input.attrs)?;
+
     let repr = match input.data {
-        Data::Struct(struct_data) => Repr::Struct(VariantRepr::repr_for(&struct_data.fields)?),
-        Data::Enum(enum_data) => Repr::Enum(
-            enum_data
+        Data::Struct(struct_data) => {
+            if let Some(tag) = &container.tag {
+                return Err(syn::Error::new(
+                    tag.span(),
+                    "tags can only be set for enums",
+                ));
+            }
+
+            let var_repr = VariantRepr::repr_for(&struct_data.fields, container.rename_all)?;
+            let struct_repr = StructRepr::parse(container.repr.as_ref(), &var_repr)?;
+            Repr::Struct(struct_repr, var_repr)
+        }
+        Data::Enum(enum_data) => {
+            let variants = enum_data
                 .variants
                 .iter()
                 .map(|variant| {
-                    Ok((
-                        variant.ident.clone(),
-                        VariantRepr::repr_for(&variant.fields)?,
-                    ))
+                    let ident = variant.ident.clone();
+                    let name = match container.rename_all {
+                        Some(rule) => rule.apply_to_variant(&ident.to_string()),
+                        None => ident.to_string(),
+                    };
+                    Ok(EnumVariant {
+                        ident,
+                        name,
+                        repr: VariantRepr::repr_for(&variant.fields, None)?,
+                    })
                 })
-                .collect::<Result<_, syn::Error>>()?,
-        ),
+                .collect::<Result<Vec<_>, syn::Error>>()?;
+            let enum_repr =
+                EnumRepr::parse(container.repr.as_ref(), container.tag.as_ref(), &variants)?;
+            Repr::Enum(enum_repr, variants)
+        }
         Data::Union(_) => {
             return Err(syn::Error::new(
                 input.span(),
@@ -78,12 +103,25 @@ pub(crate) fn parse_derive_input(
         }
     };
 
+    if container.deny_unknown_fields {
+        if let Some(field) = repr.fields().find(|f| f.attr.flatten) {
+            return Err(syn::Error::new(
+                field.ident.span(),
+                "flattened fields cannot be combined with deny_unknown_fields",
+            ));
+        }
+    }
+
     let generics = extend_bounds(input.generics, &repr, bound, dir);
 
     Ok(DeriveData {
         ident: input.ident,
         repr,
         generics,
+        from_options: FromVariantOptions {
+            deny_unknown_fields: container.deny_unknown_fields,
+            collect_errors: container.collect_errors,
+        },
     })
 }
 
@@ -0,0 +1,136 @@
+/// Case conventions accepted by `#[variant(rename_all = "...")]`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    pub(crate) const VALID_NAMES: &'static str = "lowercase, UPPERCASE, PascalCase, camelCase, snake_case, SCREAMING_SNAKE_CASE, kebab-case, SCREAMING-KEBAB-CASE";
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        let rule = match name {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            _ => return None,
+        };
+        Some(rule)
+    }
+
+    /// Renames a field, which is expected to be in `snake_case`.
+    pub(crate) fn apply_to_field(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_string(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal => {
+                let mut pascal = String::with_capacity(field.len());
+                let mut capitalize = true;
+                for c in field.chars() {
+                    if c == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(c);
+                    }
+                }
+                pascal
+            }
+            Self::Camel => {
+                let pascal = Self::Pascal.apply_to_field(field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => pascal,
+                }
+            }
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.replace('_', "-").to_ascii_uppercase(),
+        }
+    }
+
+    /// Renames an enum variant, which is expected to be in `PascalCase`.
+    pub(crate) fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            Self::Pascal => variant.to_string(),
+            Self::Lower => variant.to_ascii_lowercase(),
+            Self::Upper => variant.to_ascii_uppercase(),
+            Self::Camel => {
+                let mut chars = variant.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => variant.to_string(),
+                }
+            }
+            Self::Snake => {
+                let mut snake = String::with_capacity(variant.len() * 2);
+                for (i, c) in variant.char_indices() {
+                    if i > 0 && c.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(c.to_ascii_lowercase());
+                }
+                snake
+            }
+            Self::ScreamingSnake => Self::Snake.apply_to_variant(variant).to_ascii_uppercase(),
+            Self::Kebab => Self::Snake.apply_to_variant(variant).replace('_', "-"),
+            Self::ScreamingKebab => Self::ScreamingSnake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenameRule::{self, *};
+
+    #[test]
+    fn rename_fields() {
+        let cases: &[(RenameRule, &str)] = &[
+            (Lower, "outcome_key"),
+            (Upper, "OUTCOME_KEY"),
+            (Pascal, "OutcomeKey"),
+            (Camel, "outcomeKey"),
+            (Snake, "outcome_key"),
+            (ScreamingSnake, "OUTCOME_KEY"),
+            (Kebab, "outcome-key"),
+            (ScreamingKebab, "OUTCOME-KEY"),
+        ];
+
+        for (rule, expected) in cases {
+            assert_eq!(*expected, rule.apply_to_field("outcome_key"), "{:?}", rule);
+        }
+    }
+
+    #[test]
+    fn rename_variants() {
+        let cases: &[(RenameRule, &str)] = &[
+            (Lower, "outcomekey"),
+            (Upper, "OUTCOMEKEY"),
+            (Pascal, "OutcomeKey"),
+            (Camel, "outcomeKey"),
+            (Snake, "outcome_key"),
+            (ScreamingSnake, "OUTCOME_KEY"),
+            (Kebab, "outcome-key"),
+            (ScreamingKebab, "OUTCOME-KEY"),
+        ];
+
+        for (rule, expected) in cases {
+            assert_eq!(*expected, rule.apply_to_variant("OutcomeKey"), "{:?}", rule);
+        }
+    }
+}
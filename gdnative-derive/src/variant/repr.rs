@@ -1,13 +1,54 @@
 use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
-use syn::{Fields, Ident, Type};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{Fields, Ident, LitStr, Type};
 
-use super::attr::{Attr, AttrBuilder};
+use super::attr::{Attr, AttrBuilder, FieldDefault};
+use super::rename::RenameRule;
 use super::ToVariantTrait;
 
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub(crate) enum Repr {
-    Struct(VariantRepr),
-    Enum(Vec<(Ident, VariantRepr)>),
+    Struct(StructRepr, VariantRepr),
+    Enum(EnumRepr, Vec<EnumVariant>),
+}
+
+/// Representation of structs with named fields, set with `#[variant(repr = "...")]`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum StructRepr {
+    /// `{ "a": a, "b": b }`
+    Dictionary,
+    /// `[a, b]`
+    Array,
+}
+
+/// Representation of enums, set with `#[variant(repr = "...")]` or `#[variant(tag = "...")]`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub(crate) enum EnumRepr {
+    /// `{ "Variant": content }`
+    ExternallyTagged,
+    /// `{ "tag": "Variant", "a": a, "b": b }`
+    InternallyTagged(String),
+    /// `content`
+    Untagged,
+    /// The integer discriminant of each variant.
+    Int,
+}
+
+/// Options of the derived `FromVariant` implementation, set with container attributes.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub(crate) struct FromVariantOptions {
+    /// `#[variant(deny_unknown_fields)]`
+    pub deny_unknown_fields: bool,
+    /// `#[variant(collect_errors)]`
+    pub collect_errors: bool,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub(crate) struct EnumVariant {
+    pub ident: Ident,
+    pub name: String,
+    pub repr: VariantRepr,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -20,6 +61,7 @@ pub(crate) enum VariantRepr {
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub(crate) struct Field {
     pub ident: Ident,
+    pub name: String,
     pub ty: Type,
     pub attr: Attr,
 }
@@ -49,21 +91,220 @@ where
         .done()
 }
 
+impl Repr {
+    /// Returns all fields of the struct, or of all variants of the enum.
+    pub(crate) fn fields(&self) -> impl Iterator<Item = &Field> {
+        let reprs: Vec<&VariantRepr> = match self {
+            Repr::Struct(_, var_repr) => vec![var_repr],
+            Repr::Enum(_, variants) => variants.iter().map(|v| &v.repr).collect(),
+        };
+        reprs.into_iter().flat_map(|var_repr| var_repr.fields())
+    }
+}
+
+impl StructRepr {
+    pub(crate) fn parse(repr: Option<&LitStr>, var_repr: &VariantRepr) -> Result<Self, syn::Error> {
+        let repr = match repr {
+            Some(repr) => repr,
+            None => return Ok(StructRepr::Dictionary),
+        };
+
+        let fields = match var_repr {
+            VariantRepr::Struct(fields) => fields,
+            _ => {
+                return Err(syn::Error::new(
+                    repr.span(),
+                    "representations can only be set for structs with named fields",
+                ))
+            }
+        };
+
+        match repr.value().as_str() {
+            "dictionary" => Ok(StructRepr::Dictionary),
+            "array" => {
+                if let Some(field) = fields
+                    .iter()
+                    .find(|f| f.attr.default.is_some() || f.attr.flatten)
+                {
+                    return Err(syn::Error::new(
+                        field.ident.span(),
+                        "default and flatten are not supported for structs represented as arrays",
+                    ));
+                }
+                Ok(StructRepr::Array)
+            }
+            _ => Err(syn::Error::new(
+                repr.span(),
+                "unknown struct representation, expected one of:\n\tdictionary, array",
+            )),
+        }
+    }
+}
+
+impl EnumRepr {
+    pub(crate) fn parse(
+        repr: Option<&LitStr>,
+        tag: Option<&LitStr>,
+        variants: &[EnumVariant],
+    ) -> Result<Self, syn::Error> {
+        if let Some(tag) = tag {
+            if let Some(repr) = repr {
+                return Err(syn::Error::new(
+                    repr.span(),
+                    "internally tagged enums cannot have another representation",
+                ));
+            }
+
+            let tag_name = tag.value();
+            for variant in variants {
+                match &variant.repr {
+                    VariantRepr::Unit => {}
+                    VariantRepr::Struct(fields) => {
+                        if let Some(field) = fields.iter().find(|f| f.name == tag_name) {
+                            return Err(syn::Error::new(
+                                field.ident.span(),
+                                format!("field name conflicts with the tag \"{}\"", tag_name),
+                            ));
+                        }
+                    }
+                    VariantRepr::Tuple(_) => {
+                        return Err(syn::Error::new(
+                            variant.ident.span(),
+                            "internally tagged enums can only have unit and struct variants",
+                        ))
+                    }
+                }
+            }
+
+            return Ok(EnumRepr::InternallyTagged(tag_name));
+        }
+
+        let repr = match repr {
+            Some(repr) => repr,
+            None => return Ok(EnumRepr::ExternallyTagged),
+        };
+
+        match repr.value().as_str() {
+            "external" => Ok(EnumRepr::ExternallyTagged),
+            "untagged" => Ok(EnumRepr::Untagged),
+            "int" => {
+                if let Some(variant) = variants.iter().find(|v| v.repr != VariantRepr::Unit) {
+                    return Err(syn::Error::new(
+                        variant.ident.span(),
+                        "enums represented as integers can only have unit variants",
+                    ));
+                }
+                Ok(EnumRepr::Int)
+            }
+            _ => Err(syn::Error::new(
+                repr.span(),
+                "unknown enum representation, expected one of:\n\texternal, untagged, int",
+            )),
+        }
+    }
+}
+
+/// Returns whether `ty` is known to never be represented as a `Dictionary`, so it cannot be
+/// flattened. Types that are not known are checked when converting instead.
+fn is_never_dictionary(ty: &syn::Type) -> bool {
+    const NON_DICTIONARY_TYPES: &[&str] = &[
+        "bool",
+        "i8",
+        "i16",
+        "i32",
+        "i64",
+        "i128",
+        "isize",
+        "u8",
+        "u16",
+        "u32",
+        "u64",
+        "u128",
+        "usize",
+        "f32",
+        "f64",
+        "char",
+        "str",
+        "String",
+        "GodotString",
+        "Vec",
+        "VecDeque",
+        "VariantArray",
+        "PoolArray",
+    ];
+
+    match ty {
+        syn::Type::Array(_) | syn::Type::Slice(_) | syn::Type::Tuple(_) => true,
+        syn::Type::Paren(paren) => is_never_dictionary(&paren.elem),
+        syn::Type::Reference(reference) => is_never_dictionary(&reference.elem),
+        syn::Type::Path(path) if path.qself.is_none() => {
+            path.path.segments.last().map_or(false, |segment| {
+                NON_DICTIONARY_TYPES.contains(&segment.ident.to_string().as_str())
+            })
+        }
+        _ => false,
+    }
+}
+
+/// Returns whether `ty` is an `Option`, which is flattened as no entries when it is `None`.
+fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Paren(paren) => is_option(&paren.elem),
+        syn::Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
 impl VariantRepr {
-    pub(crate) fn repr_for(fields: &Fields) -> Result<Self, syn::Error> {
+    pub(crate) fn repr_for(
+        fields: &Fields,
+        rename_all: Option<RenameRule>,
+    ) -> Result<Self, syn::Error> {
         let this = match fields {
-            Fields::Named(fields) => VariantRepr::Struct(
-                fields
+            Fields::Named(fields) => {
+                let fields = fields
                     .named
                     .iter()
                     .map(|f| {
                         let ident = f.ident.clone().expect("fields should be named");
                         let ty = f.ty.clone();
                         let attr = parse_attrs(&f.attrs)?;
-                        Ok(Field { ident, ty, attr })
+                        if attr.flatten && is_never_dictionary(&ty) {
+                            return Err(syn::Error::new(
+                                ty.span(),
+                                "flatten is only supported for fields represented as dictionaries",
+                            ));
+                        }
+                        let name = match (&attr.rename, rename_all) {
+                            (Some(rename), _) => rename.clone(),
+                            (None, Some(rule)) => rule.apply_to_field(&ident.to_string()),
+                            (None, None) => ident.to_string(),
+                        };
+                        Ok(Field {
+                            ident,
+                            name,
+                            ty,
+                            attr,
+                        })
                     })
-                    .collect::<Result<Vec<_>, syn::Error>>()?,
-            ),
+                    .collect::<Result<Vec<_>, syn::Error>>()?;
+
+                let flattened = fields.iter().filter(|f| f.attr.flatten).count();
+                if let Some(f) = fields.iter().find(|f| f.attr.flatten && is_option(&f.ty)) {
+                    if flattened > 1 {
+                        return Err(syn::Error::new(
+                            f.ty.span(),
+                            "flattened `Option` fields cannot be combined with other flattened fields",
+                        ));
+                    }
+                }
+
+                VariantRepr::Struct(fields)
+            }
             Fields::Unnamed(fields) => VariantRepr::Tuple(
                 fields
                     .unnamed
@@ -73,7 +314,18 @@ impl VariantRepr {
                         let ident = Ident::new(&format!("__field_{}", n), Span::call_site());
                         let ty = f.ty.clone();
                         let attr = parse_attrs(&f.attrs)?;
-                        Ok(Field { ident, ty, attr })
+                        if attr.rename.is_some() || attr.default.is_some() || attr.flatten {
+                            return Err(syn::Error::new(
+                                f.span(),
+                                "rename, default and flatten are only supported on named fields",
+                            ));
+                        }
+                        Ok(Field {
+                            ident,
+                            name: n.to_string(),
+                            ty,
+                            attr,
+                        })
                     })
                     .collect::<Result<_, syn::Error>>()?,
             ),
@@ -101,6 +353,28 @@ impl VariantRepr {
         }
     }
 
+    /// Returns an expression constructing the value from the fields bound to their idents.
+    fn construct(&self, ctor: &TokenStream2) -> TokenStream2 {
+        match self {
+            VariantRepr::Unit => quote! { #ctor },
+            VariantRepr::Tuple(fields) => {
+                let idents = fields.iter().map(|f| &f.ident);
+                quote! { #ctor( #( #idents ),* ) }
+            }
+            VariantRepr::Struct(fields) => {
+                let idents = fields.iter().map(|f| &f.ident);
+                quote! { #ctor { #( #idents ),* } }
+            }
+        }
+    }
+
+    fn fields(&self) -> &[Field] {
+        match self {
+            VariantRepr::Unit => &[],
+            VariantRepr::Tuple(fields) | VariantRepr::Struct(fields) => fields,
+        }
+    }
+
     pub(crate) fn make_to_variant_expr(
         &self,
         trait_kind: ToVariantTrait,
@@ -121,59 +395,102 @@ impl VariantRepr {
                     }
                     field.make_to_variant_expr(trait_kind)
                 } else {
-                    let exprs = fields.iter().filter_map(|f| {
-                        if f.attr.skip_to_variant {
-                            None
-                        } else {
-                            Some(f.make_to_variant_expr(trait_kind))
-                        }
-                    });
+                    self.make_to_array_expr(trait_kind)
+                }
+            }
+            VariantRepr::Struct(_) => {
+                let inserts = self.make_insert_fields_stmts(trait_kind);
 
-                    quote! {
-                        {
-                            let __array = ::gdnative::core_types::VariantArray::new();
-                            #(
-                                __array.push(&#exprs);
-                            )*
-                            __array.into_shared().to_variant()
-                        }
+                quote! {
+                    {
+                        let __dict = ::gdnative::core_types::Dictionary::new();
+                        #inserts
+                        __dict.into_shared().to_variant()
                     }
                 }
             }
-            VariantRepr::Struct(fields) => {
-                let fields: Vec<&Field> =
-                    fields.iter().filter(|f| !f.attr.skip_to_variant).collect();
+        };
 
-                let name_strings: Vec<String> =
-                    fields.iter().map(|f| format!("{}", &f.ident)).collect();
+        Ok(tokens)
+    }
 
-                let name_string_literals =
-                    name_strings.iter().map(|string| Literal::string(string));
+    /// Returns an expression converting the non-skipped fields to a `VariantArray`, in order.
+    pub(crate) fn make_to_array_expr(&self, trait_kind: ToVariantTrait) -> TokenStream2 {
+        let exprs = self.fields().iter().filter_map(|f| {
+            if f.attr.skip_to_variant {
+                None
+            } else {
+                Some(f.make_to_variant_expr(trait_kind))
+            }
+        });
 
-                let exprs = fields.iter().map(|f| f.make_to_variant_expr(trait_kind));
+        quote! {
+            {
+                let __array = ::gdnative::core_types::VariantArray::new();
+                #(
+                    __array.push(&#exprs);
+                )*
+                __array.into_shared().to_variant()
+            }
+        }
+    }
+
+    /// Returns statements inserting the non-skipped fields into a `Dictionary` named `__dict`,
+    /// by name. The entries of flattened fields are inserted directly. Flattened fields that are
+    /// neither a `Dictionary` nor `nil` are reported with `godot_error!` and add no entries.
+    pub(crate) fn make_insert_fields_stmts(&self, trait_kind: ToVariantTrait) -> TokenStream2 {
+        let fields: Vec<&Field> = self
+            .fields()
+            .iter()
+            .filter(|f| !f.attr.skip_to_variant)
+            .collect();
 
+        let stmts = fields.iter().map(|f| {
+            let expr = f.make_to_variant_expr(trait_kind);
+
+            if f.attr.flatten {
+                let message = Literal::string(&format!(
+                    "flattened field `{}` is not represented as a Dictionary, skipping its entries",
+                    f.ident.unraw()
+                ));
                 quote! {
                     {
-                        let __dict = ::gdnative::core_types::Dictionary::new();
-                        #(
-                            {
-                                let __key = ::gdnative::core_types::GodotString::from(#name_string_literals).to_variant();
-                                __dict.insert(&__key, &#exprs);
+                        let __flattened = #expr;
+                        if !__flattened.is_nil() {
+                            match ::gdnative::core_types::Dictionary::from_variant(&__flattened) {
+                                Ok(__flattened) => {
+                                    for (__key, __value) in __flattened.iter() {
+                                        __dict.insert(&__key, &__value);
+                                    }
+                                }
+                                Err(_) => {
+                                    ::gdnative::log::godot_error!(#message);
+                                }
                             }
-                        )*
-                        __dict.into_shared().to_variant()
+                        }
+                    }
+                }
+            } else {
+                let name_string_literal = Literal::string(&f.name);
+                quote! {
+                    {
+                        let __key = ::gdnative::core_types::GodotString::from(#name_string_literal).to_variant();
+                        __dict.insert(&__key, &#expr);
                     }
                 }
             }
-        };
+        });
 
-        Ok(tokens)
+        quote! {
+            #( #stmts )*
+        }
     }
 
     pub(crate) fn make_from_variant_expr(
         &self,
         variant: &Ident,
         ctor: &TokenStream2,
+        options: FromVariantOptions,
     ) -> Result<TokenStream2, syn::Error> {
         let tokens = match self {
             VariantRepr::Unit => {
@@ -206,117 +523,318 @@ impl VariantRepr {
                         }
                     }
                 } else {
-                    let skipped_fields: Vec<&Field> =
-                        fields.iter().filter(|f| f.attr.skip_from_variant).collect();
+                    self.make_from_array_expr(variant, ctor, options)
+                }
+            }
+            VariantRepr::Struct(_) => {
+                let from_dict = self.make_from_dict_body(ctor, options, None);
 
-                    let non_skipped_fields: Vec<&Field> = fields
-                        .iter()
-                        .filter(|f| !f.attr.skip_from_variant)
-                        .collect();
+                quote! {
+                    {
+                        ::gdnative::core_types::Dictionary::from_variant(#variant)
+                            .map_err(|__err| FVE::InvalidStructRepr {
+                                expected: VariantStructRepr::Struct,
+                                error: std::boxed::Box::new(__err),
+                            })
+                            .and_then(|__dict| {
+                                #from_dict
+                            })
+                    }
+                }
+            }
+        };
 
-                    let skipped_idents = skipped_fields.iter().map(|f| &f.ident);
-                    let non_skipped_idents = non_skipped_fields.iter().map(|f| &f.ident);
-                    let ctor_idents = fields.iter().map(|f| &f.ident);
+        Ok(tokens)
+    }
 
-                    let expected_len = Literal::usize_suffixed(non_skipped_fields.len());
-                    let indices = (0..non_skipped_fields.len() as i32).map(Literal::i32_suffixed);
+    /// Returns an expression converting a `VariantArray` of the non-skipped fields, in order.
+    pub(crate) fn make_from_array_expr(
+        &self,
+        variant: &Ident,
+        ctor: &TokenStream2,
+        options: FromVariantOptions,
+    ) -> TokenStream2 {
+        let fields = self.fields();
 
-                    let expr_variant = &quote!(&__array.get(__index));
-                    let non_skipped_exprs = non_skipped_fields
-                        .iter()
-                        .map(|f| f.make_from_variant_expr(expr_variant));
+        let non_skipped_fields: Vec<&Field> = fields
+            .iter()
+            .filter(|f| !f.attr.skip_from_variant)
+            .collect();
 
-                    quote! {
-                        {
-                            ::gdnative::core_types::VariantArray::from_variant(#variant)
-                                .map_err(|__err| FVE::InvalidStructRepr {
-                                    expected: VariantStructRepr::Tuple,
-                                    error: std::boxed::Box::new(__err),
-                                })
-                                .and_then(|__array| {
-                                    let __expected = #expected_len;
-                                    let __len = __array.len() as usize;
-                                    if __len != __expected {
-                                        Err(FVE::InvalidLength { expected: __expected, len: __len })
-                                    }
-                                    else {
-                                        #(
-                                            let __index = #indices;
-                                            let #non_skipped_idents = #non_skipped_exprs
-                                                .map_err(|err| FVE::InvalidItem {
-                                                    index: __index as usize,
-                                                    error: std::boxed::Box::new(err),
-                                                })?;
-                                        )*
-                                        #(
-                                            let #skipped_idents = std::default::Default::default();
-                                        )*
-                                        Ok(#ctor( #(#ctor_idents),* ))
-                                    }
-                                })
+        let expected_len = Literal::usize_suffixed(non_skipped_fields.len());
+
+        let expr_variant = &quote!(&__array.get(__index));
+        let non_skipped_stmts = non_skipped_fields.iter().enumerate().map(|(index, f)| {
+            let index = Literal::i32_suffixed(index as i32);
+            let expr = f.make_from_variant_expr(expr_variant);
+            let bind = options.bind_field(
+                &f.ident,
+                quote! {
+                    #expr.map_err(|err| FVE::InvalidItem {
+                        index: __index as usize,
+                        error: std::boxed::Box::new(err),
+                    })
+                },
+            );
+            quote! {
+                let __index = #index;
+                #bind
+            }
+        });
+
+        let init_errors = options.init_errors();
+        let finish = self.make_finish_stmts(ctor, options);
+
+        quote! {
+            {
+                ::gdnative::core_types::VariantArray::from_variant(#variant)
+                    .map_err(|__err| FVE::InvalidStructRepr {
+                        expected: VariantStructRepr::Tuple,
+                        error: std::boxed::Box::new(__err),
+                    })
+                    .and_then(|__array| {
+                        let __expected = #expected_len;
+                        let __len = __array.len() as usize;
+                        if __len != __expected {
+                            Err(FVE::InvalidLength { expected: __expected, len: __len })
                         }
+                        else {
+                            #init_errors
+                            #( #non_skipped_stmts )*
+                            #finish
+                        }
+                    })
+            }
+        }
+    }
+
+    /// Returns statements converting the fields from a `Dictionary` named `__dict`, ending with
+    /// the `Result` of the constructed value.
+    ///
+    /// If unknown fields are denied, keys other than the field names and `tag` are errors.
+    pub(crate) fn make_from_dict_body(
+        &self,
+        ctor: &TokenStream2,
+        options: FromVariantOptions,
+        tag: Option<&str>,
+    ) -> TokenStream2 {
+        let fields = self.fields();
+
+        let non_skipped_fields: Vec<&Field> = fields
+            .iter()
+            .filter(|f| !f.attr.skip_from_variant)
+            .collect();
+
+        let check_unknown_fields = if options.deny_unknown_fields {
+            let known_names = tag
+                .into_iter()
+                .chain(
+                    fields
+                        .iter()
+                        .filter(|f| !(f.attr.skip_to_variant && f.attr.skip_from_variant))
+                        .map(|f| f.name.as_str()),
+                )
+                .map(Literal::string);
+
+            let report = options.report_error(quote! {
+                FVE::UnknownField {
+                    field_name: __key.to_string(),
+                    expected: __known_fields,
+                }
+            });
+
+            quote! {
+                let __known_fields: &'static [&'static str] = &[#( #known_names ),*];
+                let __keys = __dict.keys();
+                for __index in 0..__keys.len() {
+                    let __key = __keys.get(__index);
+                    let __is_known = String::from_variant(&__key)
+                        .map_or(false, |__key| __known_fields.contains(&__key.as_str()));
+                    if !__is_known {
+                        #report
                     }
                 }
             }
-            VariantRepr::Struct(fields) => {
-                let skipped_fields: Vec<&Field> =
-                    fields.iter().filter(|f| f.attr.skip_from_variant).collect();
-
-                let non_skipped_fields: Vec<&Field> = fields
-                    .iter()
-                    .filter(|f| !f.attr.skip_from_variant)
-                    .collect();
+        } else {
+            quote! {}
+        };
 
-                let skipped_idents = skipped_fields.iter().map(|f| &f.ident);
-                let non_skipped_idents: Vec<&Ident> =
-                    non_skipped_fields.iter().map(|f| &f.ident).collect();
-                let ctor_idents = fields.iter().map(|f| &f.ident);
+        let expr_variant = &quote!(&__dict.get_or_nil(&__key));
+        let non_skipped_stmts = non_skipped_fields.iter().map(|f| {
+            if f.attr.flatten {
+                let expr = f.make_from_variant_expr(&quote!(&__dict.to_variant()));
+                if !is_option(&f.ty) {
+                    return options.bind_field(&f.ident, expr);
+                }
 
-                let name_strings: Vec<String> = non_skipped_idents
-                    .iter()
-                    .map(|ident| format!("{}", ident))
-                    .collect();
+                // `None` adds no entries, so it is read back when there are no entries besides
+                // the ones of the containing type
+                let own_names = tag
+                    .into_iter()
+                    .chain(
+                        fields
+                            .iter()
+                            .filter(|field| !field.attr.flatten)
+                            .filter(|field| {
+                                !(field.attr.skip_to_variant && field.attr.skip_from_variant)
+                            })
+                            .map(|field| field.name.as_str()),
+                    )
+                    .map(Literal::string);
 
-                let name_string_literals =
-                    name_strings.iter().map(|string| Literal::string(string));
+                let expr = quote! {
+                    {
+                        let __own_fields: &'static [&'static str] = &[#( #own_names ),*];
+                        let __keys = __dict.keys();
+                        let __has_flattened_entries = (0..__keys.len()).any(|__index| {
+                            String::from_variant(&__keys.get(__index))
+                                .map_or(true, |__key| !__own_fields.contains(&__key.as_str()))
+                        });
+                        if __has_flattened_entries {
+                            #expr
+                        } else {
+                            Ok(::std::option::Option::None)
+                        }
+                    }
+                };
+                return options.bind_field(&f.ident, expr);
+            }
 
-                let expr_variant = &quote!(&__dict.get_or_nil(&__key));
-                let exprs = non_skipped_fields
-                    .iter()
-                    .map(|f| f.make_from_variant_expr(expr_variant));
+            let name_string_literal = Literal::string(&f.name);
+            let expr = f.make_from_variant_expr(expr_variant);
+            let expr = quote! {
+                #expr.map_err(|err| FVE::InvalidField {
+                    field_name: __field_name,
+                    error: std::boxed::Box::new(err),
+                })
+            };
 
+            let expr = if f.attr.default.is_some() {
+                let default_expr = f.default_expr();
                 quote! {
-                    {
-                        ::gdnative::core_types::Dictionary::from_variant(#variant)
-                            .map_err(|__err| FVE::InvalidStructRepr {
-                                expected: VariantStructRepr::Struct,
-                                error: std::boxed::Box::new(__err),
-                            })
-                            .and_then(|__dict| {
-                                #(
-                                    let __field_name = #name_string_literals;
-                                    let __key = ::gdnative::core_types::GodotString::from(__field_name).to_variant();
-                                    let #non_skipped_idents = #exprs
-                                        .map_err(|err| FVE::InvalidField {
-                                            field_name: __field_name,
-                                            error: std::boxed::Box::new(err),
-                                        })?;
-                                )*
-                                #(
-                                    let #skipped_idents = std::default::Default::default();
-                                )*
-                                Ok(#ctor { #( #ctor_idents ),* })
-                            })
+                    if __dict.contains(&__key) {
+                        #expr
+                    } else {
+                        Ok(#default_expr)
                     }
                 }
+            } else {
+                expr
+            };
+
+            let bind = options.bind_field(&f.ident, expr);
+
+            quote! {
+                let __field_name = #name_string_literal;
+                let __key = ::gdnative::core_types::GodotString::from(__field_name).to_variant();
+                #bind
+            }
+        });
+
+        let init_errors = options.init_errors();
+        let finish = self.make_finish_stmts(ctor, options);
+
+        quote! {
+            #init_errors
+            #check_unknown_fields
+            #( #non_skipped_stmts )*
+            #finish
+        }
+    }
+
+    /// Returns statements binding the skipped fields to their defaults, ending with the
+    /// `Result` of the constructed value.
+    fn make_finish_stmts(&self, ctor: &TokenStream2, options: FromVariantOptions) -> TokenStream2 {
+        let fields = self.fields();
+
+        let skipped_fields: Vec<&Field> =
+            fields.iter().filter(|f| f.attr.skip_from_variant).collect();
+
+        let skipped_idents = skipped_fields.iter().map(|f| &f.ident);
+        let skipped_defaults = skipped_fields.iter().map(|f| f.default_expr());
+        let construct = self.construct(ctor);
+
+        let result = if options.collect_errors {
+            // Fields that failed to convert are `None`, with the errors in `__errors`
+            let non_skipped_idents: Vec<&Ident> = fields
+                .iter()
+                .filter(|f| !f.attr.skip_from_variant)
+                .map(|f| &f.ident)
+                .collect();
+
+            quote! {
+                match ( #( #non_skipped_idents, )* ) {
+                    ( #( Some(#non_skipped_idents), )* ) if __errors.is_empty() => Ok(#construct),
+                    _ => Err(if __errors.len() == 1 {
+                        __errors.remove(0)
+                    } else {
+                        FVE::Multiple(__errors)
+                    }),
+                }
             }
+        } else {
+            quote! { Ok(#construct) }
         };
 
-        Ok(tokens)
+        quote! {
+            #(
+                let #skipped_idents = #skipped_defaults;
+            )*
+            #result
+        }
+    }
+}
+
+impl FromVariantOptions {
+    /// Returns statements declaring `__errors`, if errors are collected.
+    fn init_errors(self) -> TokenStream2 {
+        if self.collect_errors {
+            quote! {
+                let mut __errors: std::vec::Vec<FVE> = std::vec::Vec::new();
+            }
+        } else {
+            quote! {}
+        }
+    }
+
+    /// Returns a statement binding `ident` to the value of the `Result` expression `expr`.
+    /// Errors are returned early, or collected in `__errors` with `ident` bound to `None`.
+    fn bind_field(self, ident: &Ident, expr: TokenStream2) -> TokenStream2 {
+        if self.collect_errors {
+            quote! {
+                let #ident = match #expr {
+                    Ok(__value) => Some(__value),
+                    Err(__err) => {
+                        __errors.push(__err);
+                        None
+                    }
+                };
+            }
+        } else {
+            quote! {
+                let #ident = #expr?;
+            }
+        }
+    }
+
+    /// Returns a statement returning the error `expr` early, or collecting it in `__errors`.
+    fn report_error(self, expr: TokenStream2) -> TokenStream2 {
+        if self.collect_errors {
+            quote! { __errors.push(#expr); }
+        } else {
+            quote! { return Err(#expr); }
+        }
     }
 }
 
 impl Field {
+    fn default_expr(&self) -> TokenStream2 {
+        match &self.attr.default {
+            Some(FieldDefault::Path(path)) => quote!(#path()),
+            Some(FieldDefault::Default) | None => quote!(std::default::Default::default()),
+        }
+    }
+
     fn make_to_variant_expr(&self, trait_kind: ToVariantTrait) -> TokenStream2 {
         let Field { ident, attr, .. } = self;
         if let Some(to_variant_with) = &attr.to_variant_with {
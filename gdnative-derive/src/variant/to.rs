@@ -1,6 +1,6 @@
 use proc_macro2::{Literal, TokenStream as TokenStream2};
 
-use super::repr::Repr;
+use super::repr::{EnumRepr, Repr, StructRepr, VariantRepr};
 use super::{DeriveData, ToVariantTrait};
 
 pub(crate) fn expand_to_variant(
@@ -11,6 +11,7 @@ pub(crate) fn expand_to_variant(
         ident,
         repr,
         mut generics,
+        ..
     } = derive_data;
 
     let trait_path = trait_kind.trait_path();
@@ -23,9 +24,12 @@ pub(crate) fn expand_to_variant(
     }
 
     let return_expr = match repr {
-        Repr::Struct(var_repr) => {
+        Repr::Struct(struct_repr, var_repr) => {
             let destructure_pattern = var_repr.destructure_pattern();
-            let to_variant = var_repr.make_to_variant_expr(trait_kind)?;
+            let to_variant = match struct_repr {
+                StructRepr::Dictionary => var_repr.make_to_variant_expr(trait_kind)?,
+                StructRepr::Array => var_repr.make_to_array_expr(trait_kind),
+            };
             quote! {
                 {
                     let #ident #destructure_pattern = self;
@@ -33,7 +37,7 @@ pub(crate) fn expand_to_variant(
                 }
             }
         }
-        Repr::Enum(variants) => {
+        Repr::Enum(enum_repr, variants) => {
             if variants.is_empty() {
                 quote! {
                     unreachable!("this is an uninhabitable enum");
@@ -41,21 +45,54 @@ pub(crate) fn expand_to_variant(
             } else {
                 let match_arms = variants
                     .iter()
-                    .map(|(var_ident, var_repr)| {
+                    .map(|variant| {
+                        let var_ident = &variant.ident;
+                        let var_repr = &variant.repr;
                         let destructure_pattern = var_repr.destructure_pattern();
-                        let to_variant = var_repr.make_to_variant_expr(trait_kind)?;
-                        let var_ident_string = format!("{}", var_ident);
-                        let var_ident_string_literal = Literal::string(&var_ident_string);
-                        let tokens = quote! {
-                            #ident::#var_ident #destructure_pattern => {
-                                let __dict = ::gdnative::core_types::Dictionary::new();
-                                let __key = ::gdnative::core_types::ToVariant::to_variant(
-                                    &::gdnative::core_types::GodotString::from(#var_ident_string_literal)
-                                );
-                                let __value = #to_variant;
-                                __dict.insert(&__key, &__value);
-                                ::gdnative::core_types::ToVariant::to_variant(&__dict.into_shared())
+                        let var_name_literal = Literal::string(&variant.name);
+                        let to_variant = match &enum_repr {
+                            EnumRepr::ExternallyTagged => {
+                                let to_variant = var_repr.make_to_variant_expr(trait_kind)?;
+                                quote! {
+                                    {
+                                        let __dict = ::gdnative::core_types::Dictionary::new();
+                                        let __key = ::gdnative::core_types::ToVariant::to_variant(
+                                            &::gdnative::core_types::GodotString::from(#var_name_literal)
+                                        );
+                                        let __value = #to_variant;
+                                        __dict.insert(&__key, &__value);
+                                        ::gdnative::core_types::ToVariant::to_variant(&__dict.into_shared())
+                                    }
+                                }
+                            }
+                            EnumRepr::InternallyTagged(tag) => {
+                                let tag_literal = Literal::string(tag);
+                                let inserts = var_repr.make_insert_fields_stmts(trait_kind);
+                                quote! {
+                                    {
+                                        let __dict = ::gdnative::core_types::Dictionary::new();
+                                        let __key = ::gdnative::core_types::ToVariant::to_variant(
+                                            &::gdnative::core_types::GodotString::from(#tag_literal)
+                                        );
+                                        let __value = ::gdnative::core_types::ToVariant::to_variant(
+                                            &::gdnative::core_types::GodotString::from(#var_name_literal)
+                                        );
+                                        __dict.insert(&__key, &__value);
+                                        #inserts
+                                        ::gdnative::core_types::ToVariant::to_variant(&__dict.into_shared())
+                                    }
+                                }
                             }
+                            EnumRepr::Untagged => match var_repr {
+                                VariantRepr::Unit => quote! { ::gdnative::core_types::Variant::nil() },
+                                _ => var_repr.make_to_variant_expr(trait_kind)?,
+                            },
+                            EnumRepr::Int => quote! {
+                                ::gdnative::core_types::ToVariant::to_variant(&(#ident::#var_ident as i64))
+                            },
+                        };
+                        let tokens = quote! {
+                            #ident::#var_ident #destructure_pattern => #to_variant
                         };
                         Ok(tokens)
                     }).collect::<Result<Vec<_>,syn::Error>>()?;
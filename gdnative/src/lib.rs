@@ -48,6 +48,11 @@
 //! * **`async`**<br>
 //!   Activates async functionality, see [`tasks`] module for details.
 //!
+//! * **`inventory`**<br>
+//!   Enables automatic class registration with `#[auto_register]` and
+//!   [`InitHandle::add_all_classes`](init::InitHandle::add_all_classes). Requires Rust 1.68
+//!   or later, above the minimum supported Rust version of the other features.
+//!
 //! * **`serde`**<br>
 //!   Enable for `serde` support of several core types. See also [`Variant`](core_types::Variant).
 //!
@@ -11,7 +11,10 @@ fn ui_tests() {
     t.compile_fail("tests/ui/derive_fail_methods.rs");
     t.compile_fail("tests/ui/derive_fail_methods_param.rs");
     t.compile_fail("tests/ui/derive_fail_methods_list.rs");
+    t.compile_fail("tests/ui/derive_fail_methods_mixin.rs");
     t.compile_fail("tests/ui/derive_fail_methods_missing_new.rs");
+    t.compile_fail("tests/ui/derive_fail_methods_virtual_name.rs");
+    t.compile_fail("tests/ui/derive_fail_methods_virtual_arg.rs");
     t.compile_fail("tests/ui/derive_fail_userdata.rs");
     t.compile_fail("tests/ui/derive_fail_property_empty_hint.rs");
 
@@ -25,6 +28,9 @@ fn ui_tests() {
     t.compile_fail("tests/ui/to_variant_fail_05.rs");
     t.compile_fail("tests/ui/to_variant_fail_06.rs");
     t.compile_fail("tests/ui/to_variant_fail_07.rs");
+    t.compile_fail("tests/ui/to_variant_fail_08.rs");
+    t.compile_fail("tests/ui/to_variant_fail_09.rs");
+    t.compile_fail("tests/ui/to_variant_fail_10.rs");
 
     // FromVariant
     t.compile_fail("tests/ui/from_variant_fail_01.rs");
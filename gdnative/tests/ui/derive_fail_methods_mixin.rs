@@ -0,0 +1,14 @@
+use gdnative::prelude::*;
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+struct Foo {}
+
+#[methods(mixin = 42)]
+impl Foo {
+    fn new(_owner: &Node) -> Self {
+        Foo {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use gdnative::prelude::*;
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+struct Foo {}
+
+#[methods]
+impl Foo {
+    fn new(_owner: &Node) -> Self {
+        Foo {}
+    }
+
+    #[method(virtual)]
+    fn _process(&self, _delta: bool) {}
+}
+
+fn main() {}
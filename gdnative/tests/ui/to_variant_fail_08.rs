@@ -0,0 +1,9 @@
+use gdnative::prelude::*;
+
+#[derive(ToVariant)]
+#[variant(tag = "type")]
+pub enum Foo {
+    Bar(String),
+}
+
+fn main() {}
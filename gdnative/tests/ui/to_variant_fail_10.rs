@@ -0,0 +1,16 @@
+use gdnative::prelude::*;
+
+#[derive(ToVariant)]
+pub struct Bar {
+    a: i64,
+}
+
+#[derive(ToVariant)]
+pub struct Foo {
+    #[variant(flatten)]
+    bar: Option<Bar>,
+    #[variant(flatten)]
+    baz: Bar,
+}
+
+fn main() {}
@@ -36,6 +36,62 @@ pub struct Foo {
     skip_from: String,
 }
 
+#[derive(ToVariant, FromVariant)]
+#[variant(rename_all = "camelCase")]
+pub struct Renamed {
+    max_players: i64,
+    #[variant(rename = "map", default)]
+    map_name: String,
+}
+
+#[derive(ToVariant, FromVariant)]
+#[variant(deny_unknown_fields, collect_errors)]
+pub struct Strict {
+    #[variant(default = "default_version")]
+    version: i64,
+    #[variant(skip, default = "default_version")]
+    cached_version: i64,
+}
+
+fn default_version() -> i64 {
+    1
+}
+
+#[derive(ToVariant, FromVariant)]
+pub struct Flattened {
+    #[variant(flatten)]
+    renamed: Renamed,
+    extra: bool,
+}
+
+#[derive(ToVariant, FromVariant)]
+#[variant(repr = "array")]
+pub struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[derive(ToVariant, FromVariant)]
+#[variant(tag = "type", rename_all = "snake_case")]
+pub enum Shape {
+    Circle { radius: f64 },
+    Empty,
+}
+
+#[derive(ToVariant, FromVariant)]
+#[variant(repr = "untagged")]
+pub enum Value {
+    Int(i64),
+    Pair(i64, String),
+}
+
+#[derive(ToVariant, FromVariant)]
+#[variant(repr = "int")]
+pub enum Team {
+    Red = 1,
+    Blue,
+}
+
 #[derive(OwnedToVariant)]
 pub struct Owned;
 
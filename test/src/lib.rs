@@ -10,6 +10,7 @@ mod test_constructor;
 mod test_derive;
 mod test_free_ub;
 mod test_map_owned;
+mod test_pool_conversions;
 mod test_register;
 mod test_return_leak;
 mod test_serde;
@@ -47,6 +48,8 @@ pub extern "C" fn run_tests(
     status &= gdnative::core_types::test_variant_hash_map();
     status &= gdnative::core_types::test_variant_hash_set();
     status &= gdnative::core_types::test_variant_vec();
+    status &= gdnative::core_types::test_variant_std_types();
+    status &= gdnative::core_types::test_from_variant_error_path();
     status &= gdnative::core_types::test_to_variant_iter();
     status &= gdnative::core_types::test_variant_tuple();
     status &= gdnative::core_types::test_variant_dispatch();
@@ -77,6 +80,7 @@ pub extern "C" fn run_tests(
     status &= test_derive::run_tests();
     status &= test_free_ub::run_tests();
     status &= test_map_owned::run_tests();
+    status &= test_pool_conversions::run_tests();
     status &= test_register::run_tests();
     status &= test_return_leak::run_tests();
     status &= test_serde::run_tests();
@@ -234,6 +238,7 @@ fn init(handle: InitHandle) {
     test_derive::register(handle);
     test_free_ub::register(handle);
     test_map_owned::register(handle);
+    test_pool_conversions::register(handle);
     test_register::register(handle);
     test_return_leak::register(handle);
     test_vararray_return::register(handle);
@@ -48,7 +48,7 @@ impl AsyncExecutorDriver {
 
 #[methods]
 impl AsyncExecutorDriver {
-    #[method]
+    #[method(virtual)]
     fn _process(&self, _delta: f64) {
         EXECUTOR.with(|e| e.pool.borrow_mut().run_until_stalled());
     }
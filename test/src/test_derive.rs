@@ -1,16 +1,21 @@
 use std::cell::{self, Cell, RefCell};
 use std::rc::Rc;
 
-use gdnative::export::Property;
+use gdnative::export::{NativeClassCtor, Property, Signal, Super};
 use gdnative::prelude::*;
 
 pub(crate) fn run_tests() -> bool {
     let mut status = true;
 
     status &= test_derive_to_variant();
+    status &= test_derive_variant_repr();
+    status &= test_derive_variant_defaults();
+    status &= test_derive_variant_collect_errors();
     status &= test_derive_owned_to_variant();
     status &= test_derive_nativeclass();
     status &= test_derive_nativeclass_without_constructor();
+    status &= test_derive_nativeclass_with_ctor_args();
+    status &= test_derive_nativeclass_with_super_class();
     status &= test_derive_nativeclass_without_inherit();
     status &= test_derive_nativeclass_godot_attr_without_base();
     status &= test_derive_nativeclass_godot_attr_with_base();
@@ -19,6 +24,7 @@ pub(crate) fn run_tests() -> bool {
     status &= test_derive_nativeclass_godot_attr_all_arguments();
     status &= test_derive_nativeclass_with_property_get_set();
     status &= test_derive_nativeclass_property_with_only_getter();
+    status &= test_derive_nativeclass_typed_signal();
 
     status
 }
@@ -26,6 +32,9 @@ pub(crate) fn run_tests() -> bool {
 pub(crate) fn register(handle: InitHandle) {
     handle.add_class::<MinimalDerive>();
     handle.add_class::<EmplacementOnly>();
+    handle.add_class::<ConstructedWithArgs>();
+    handle.add_class::<SuperCharacter>();
+    handle.add_class::<SubEnemy>();
     handle.add_class::<WithoutInherit>();
     handle.add_class::<GodotAttrWithoutBase>();
     handle.add_class::<GodotAttrWithBase>();
@@ -34,6 +43,8 @@ pub(crate) fn register(handle: InitHandle) {
     handle.add_class::<GodotAttrAllArguments>();
     handle.add_class::<CustomGetSet>();
     handle.add_class::<MyVec>();
+    handle.add_class::<TypedSignal>();
+    handle.add_class::<TypedSignalReceiver>();
 }
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
@@ -148,6 +159,242 @@ crate::godot_itest! { test_derive_to_variant {
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
+crate::godot_itest! { test_derive_variant_repr {
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(rename_all = "camelCase")]
+    struct Settings {
+        max_players: i64,
+        #[variant(rename = "map", default)]
+        map_name: String,
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(repr = "array")]
+    struct Point {
+        x: f64,
+        y: f64,
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(tag = "type", rename_all = "snake_case")]
+    enum Shape {
+        Circle { radius: f64 },
+        Empty,
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(repr = "untagged")]
+    enum Value {
+        Nothing,
+        Int(i64),
+        Named { name: String },
+    }
+
+    #[derive(Clone, Copy, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(repr = "int")]
+    enum Team {
+        Red = 1,
+        Blue = 4,
+    }
+
+    let variant = Settings { max_players: 4, map_name: "arena".into() }.to_variant();
+    let dictionary = variant.to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(Some(4), dictionary.get("maxPlayers").and_then(|v| v.to::<i64>()));
+    assert_eq!(
+        Some("arena".into()),
+        dictionary.get("map").and_then(|v| v.to::<String>())
+    );
+
+    let dictionary = Dictionary::new();
+    dictionary.insert("maxPlayers", 2);
+    assert_eq!(
+        Ok(Settings { max_players: 2, map_name: String::new() }),
+        Settings::from_variant(&dictionary.into_shared().to_variant())
+    );
+
+    let variant = Point { x: 1.0, y: 2.0 }.to_variant();
+    let array = variant.to::<VariantArray>().expect("should be array");
+    assert_eq!(2, array.len());
+    assert_eq!(Some(2.0), array.get(1).to::<f64>());
+    assert_eq!(Ok(Point { x: 1.0, y: 2.0 }), Point::from_variant(&variant));
+
+    let variant = Shape::Circle { radius: 3.0 }.to_variant();
+    let dictionary = variant.to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(
+        Some("circle".into()),
+        dictionary.get("type").and_then(|v| v.to::<String>())
+    );
+    assert_eq!(Some(3.0), dictionary.get("radius").and_then(|v| v.to::<f64>()));
+    assert_eq!(Ok(Shape::Circle { radius: 3.0 }), Shape::from_variant(&variant));
+    assert_eq!(Ok(Shape::Empty), Shape::from_variant(&Shape::Empty.to_variant()));
+
+    assert!(Value::Nothing.to_variant().is_nil());
+    assert_eq!(Some(5), Value::Int(5).to_variant().to::<i64>());
+    for value in [Value::Nothing, Value::Int(5), Value::Named { name: "foo".into() }] {
+        assert_eq!(Ok(value.clone()), Value::from_variant(&value.to_variant()));
+    }
+    assert!(Value::from_variant(&1.0.to_variant()).is_err());
+
+    assert_eq!(Some(4), Team::Blue.to_variant().to::<i64>());
+    assert_eq!(Ok(Team::Red), Team::from_variant(&1.to_variant()));
+    assert_eq!(
+        Err(FromVariantError::UnknownEnumVariant {
+            variant: "2".into(),
+            expected: &["Red", "Blue"],
+        }),
+        Team::from_variant(&2.to_variant())
+    );
+}}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+crate::godot_itest! { test_derive_variant_defaults {
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    struct Settings {
+        volume: i64,
+        #[variant(default)]
+        muted: bool,
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    struct SaveFile {
+        #[variant(flatten)]
+        settings: Settings,
+        #[variant(default = "first_slot")]
+        slot: i64,
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(deny_unknown_fields)]
+    struct Strict {
+        volume: i64,
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    struct OptionalSettings {
+        #[variant(flatten)]
+        settings: Option<Settings>,
+        slot: i64,
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(repr = "int")]
+    enum Volume {
+        Low,
+    }
+
+    #[derive(Clone, PartialEq, Debug, ToVariant, FromVariant)]
+    struct InvalidFlatten {
+        #[variant(flatten)]
+        volume: Volume,
+    }
+
+    fn first_slot() -> i64 {
+        1
+    }
+
+    let save = SaveFile {
+        settings: Settings { volume: 5, muted: true },
+        slot: 3,
+    };
+    let variant = save.to_variant();
+    let dictionary = variant.to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(3, dictionary.len());
+    assert_eq!(Some(5), dictionary.get("volume").and_then(|v| v.to::<i64>()));
+    assert_eq!(Some(true), dictionary.get("muted").and_then(|v| v.to::<bool>()));
+    assert_eq!(Ok(save), SaveFile::from_variant(&variant));
+
+    // An older save file, without the fields added later
+    let dictionary = Dictionary::new();
+    dictionary.insert("volume", 2);
+    let variant = dictionary.into_shared().to_variant();
+    assert_eq!(
+        Ok(SaveFile {
+            settings: Settings { volume: 2, muted: false },
+            slot: 1,
+        }),
+        SaveFile::from_variant(&variant)
+    );
+    assert_eq!(Ok(Strict { volume: 2 }), Strict::from_variant(&variant));
+
+    let dictionary = Dictionary::new();
+    dictionary.insert("volume", 2);
+    dictionary.insert("muted", true);
+    assert_eq!(
+        Err(FromVariantError::UnknownField {
+            field_name: "muted".into(),
+            expected: &["volume"],
+        }),
+        Strict::from_variant(&dictionary.into_shared().to_variant())
+    );
+
+    // Flattened fields represented as nil add no entries
+    let optional = OptionalSettings { settings: None, slot: 2 };
+    let dictionary = optional.to_variant().to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(1, dictionary.len());
+    assert_eq!(Some(2), dictionary.get("slot").and_then(|v| v.to::<i64>()));
+    assert_eq!(Ok(optional), OptionalSettings::from_variant(&dictionary.to_variant()));
+
+    let optional = OptionalSettings {
+        settings: Some(Settings { volume: 3, muted: false }),
+        slot: 2,
+    };
+    assert_eq!(Ok(optional.clone()), OptionalSettings::from_variant(&optional.to_variant()));
+
+    // Other values that are not dictionaries are reported and add no entries
+    let invalid = InvalidFlatten { volume: Volume::Low };
+    let dictionary = invalid.to_variant().to::<Dictionary>().expect("should be dictionary");
+    assert!(dictionary.is_empty());
+}}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+crate::godot_itest! { test_derive_variant_collect_errors {
+    #[derive(Debug, ToVariant, FromVariant)]
+    #[variant(collect_errors)]
+    struct Stats {
+        hp: i64,
+        speed: f64,
+    }
+
+    #[derive(Debug, ToVariant, FromVariant)]
+    #[variant(collect_errors)]
+    struct Item {
+        name: String,
+        stats: Stats,
+    }
+
+    let stats = Dictionary::new();
+    stats.insert("hp", "full");
+    let item = Dictionary::new();
+    item.insert("stats", stats.into_shared());
+
+    let error = Item::from_variant(&item.into_shared().to_variant())
+        .expect_err("should fail with missing and invalid fields");
+
+    let paths: Vec<String> = error
+        .all_errors()
+        .into_iter()
+        .map(|(path, _)| format!("{:?}", path))
+        .collect();
+    assert_eq!(
+        vec![
+            r#"[Field("name")]"#,
+            r#"[Field("stats"), Field("hp")]"#,
+            r#"[Field("stats"), Field("speed")]"#,
+        ],
+        paths
+    );
+    assert_eq!(
+        "name: invalid variant type: expected GodotString, got Nil; \
+            stats.hp: invalid variant type: expected I64, got GodotString; \
+            stats.speed: invalid variant type: expected F64, got Nil",
+        error.to_compact_string()
+    );
+}}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
 crate::godot_itest! { test_derive_owned_to_variant {
     #[derive(OwnedToVariant)]
     struct ToVar {
@@ -228,6 +475,131 @@ crate::godot_itest! { test_derive_nativeclass_without_constructor {
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
+#[derive(NativeClass)]
+#[inherit(Reference)]
+#[no_constructor]
+struct ConstructedWithArgs {
+    name: String,
+    owner_id: i64,
+}
+
+#[methods]
+impl ConstructedWithArgs {
+    #[method]
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl NativeClassCtor<(String, i64)> for ConstructedWithArgs {
+    fn ctor(owner: TRef<Reference>, (name, suffix): (String, i64)) -> Self {
+        ConstructedWithArgs {
+            name: format!("{}_{}", name, suffix),
+            owner_id: owner.get_instance_id(),
+        }
+    }
+}
+
+crate::godot_itest! { test_derive_nativeclass_with_ctor_args {
+    let foo = Instance::<ConstructedWithArgs, _>::new_with((String::from("mob"), 3));
+    let owner_id = foo.base().get_instance_id();
+    assert_eq!(Ok(owner_id), foo.map(|foo, _| foo.owner_id));
+
+    let base = foo.into_base();
+    assert_eq!(Some("mob_3".to_string()), unsafe { base.call("name", &[]).to::<String>() });
+}}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+/// A character with health.
+#[derive(NativeClass)]
+struct SuperCharacter {
+    #[property]
+    health: i64,
+}
+
+impl SuperCharacter {
+    fn new(_owner: &Reference) -> Self {
+        SuperCharacter { health: 100 }
+    }
+}
+
+#[methods]
+impl SuperCharacter {
+    #[method]
+    fn take_damage(&mut self, amount: i64) -> i64 {
+        self.health -= amount;
+        self.health
+    }
+
+    #[method]
+    fn describe(&self) -> String {
+        "character".to_string()
+    }
+}
+
+#[derive(NativeClass)]
+struct SubEnemy {
+    #[super_class]
+    character: Super<SuperCharacter>,
+    #[property]
+    bounty: i64,
+}
+
+impl SubEnemy {
+    fn new(_owner: &Reference) -> Self {
+        SubEnemy {
+            character: Super::new(SuperCharacter { health: 50 }),
+            bounty: 10,
+        }
+    }
+}
+
+#[methods]
+impl SubEnemy {
+    #[method]
+    fn describe(&self, #[base] owner: TRef<Reference>) -> String {
+        let character = self
+            .character
+            .instance(owner)
+            .map(|character, _| character.describe())
+            .expect("super class data should not be borrowed");
+        format!("enemy {}", character)
+    }
+}
+
+crate::godot_itest! { test_derive_nativeclass_with_super_class {
+    let enemy = Instance::<SubEnemy, _>::new().into_shared();
+    let base = unsafe { enemy.base().assume_safe() };
+
+    // Members of the super class operate on the `Super` field
+    assert_eq!(Some(30), unsafe { base.call("take_damage", &[Variant::new(20)]).to::<i64>() });
+    assert_eq!(Some(30), base.get("health").to::<i64>());
+    assert_eq!(Some(10), base.get("bounty").to::<i64>());
+
+    // Members of the sub class override the super class ones
+    assert_eq!(
+        Some("enemy character".to_string()),
+        unsafe { base.call("describe", &[]).to::<String>() },
+    );
+
+    let character = base
+        .cast_instance::<SuperCharacter>()
+        .expect("cast to the super class should succeed");
+    assert_eq!(Ok(30), character.map(|character, _| character.health));
+    assert!(base.cast_instance::<SubEnemy>().is_some());
+
+    // The description of the super class is not inherited
+    let xml = gdnative::export::class_reference_xml::<SuperCharacter>()
+        .expect("class should be registered");
+    assert!(xml.contains("A character with health."));
+    let xml = gdnative::export::class_reference_xml::<SubEnemy>()
+        .expect("class should be registered");
+    assert!(!xml.contains("A character with health."));
+}}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
 #[derive(NativeClass)]
 struct WithoutInherit(i64);
 
@@ -479,3 +851,75 @@ crate::godot_itest! { test_derive_nativeclass_property_with_only_getter {
     let _ = std::panic::catch_unwind(|| owner.set("size", 3));
     assert_eq!(u32::from_variant(&owner.get("size")).unwrap(), 1);
 }}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+#[derive(NativeClass)]
+struct TypedSignal {
+    #[signal(params = "amount, label")]
+    progress: Signal<(i64, GodotString)>,
+    #[signal(name = "renamed_done")]
+    done: Signal<()>,
+}
+
+#[methods]
+impl TypedSignal {
+    fn new(_owner: &Reference) -> Self {
+        Self {
+            progress: Signal::default(),
+            done: Signal::default(),
+        }
+    }
+}
+
+#[derive(NativeClass)]
+struct TypedSignalReceiver {
+    received: Vec<(i64, String)>,
+    done_count: u32,
+}
+
+#[methods]
+impl TypedSignalReceiver {
+    fn new(_owner: &Reference) -> Self {
+        Self {
+            received: Vec::new(),
+            done_count: 0,
+        }
+    }
+
+    #[method]
+    fn on_progress(&mut self, amount: i64, label: String) {
+        self.received.push((amount, label));
+    }
+
+    #[method]
+    fn on_done(&mut self) {
+        self.done_count += 1;
+    }
+}
+
+crate::godot_itest! { test_derive_nativeclass_typed_signal {
+    use gdnative::export::user_data::Map;
+
+    let emitter = TypedSignal::new_instance().into_shared();
+    let emitter = unsafe { emitter.assume_safe() };
+    let receiver = TypedSignalReceiver::new_instance().into_shared();
+    let receiver = unsafe { receiver.assume_safe() };
+
+    let emitter_base = emitter.base();
+    TypedSignal::connect_progress(&emitter_base, receiver.base(), "on_progress").unwrap();
+    TypedSignal::connect_done(&emitter_base, receiver.base(), "on_done").unwrap();
+    assert!(emitter_base.has_signal("progress"));
+    assert!(emitter_base.has_signal("renamed_done"));
+
+    TypedSignal::emit_progress(&emitter_base, 42, "half".into());
+    TypedSignal::emit_done(&emitter_base);
+
+    receiver
+        .script()
+        .map(|receiver| {
+            assert_eq!(vec![(42, "half".to_string())], receiver.received);
+            assert_eq!(1, receiver.done_count);
+        })
+        .unwrap();
+}}
@@ -0,0 +1,105 @@
+use std::fmt::Debug;
+
+use gdnative::core_types::as_pool_array;
+use gdnative::core_types::PoolElement;
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_pool_conversions();
+    #[cfg(feature = "bench")]
+    {
+        status &= bench::test_pool_conversions_bench();
+    }
+
+    status
+}
+
+pub(crate) fn register(_handle: InitHandle) {}
+
+// Both the default `VariantArray` representation of numeric sequences and the `PoolArray` one
+// of `as_pool_array` must produce the same values.
+crate::godot_itest! { test_pool_conversions {
+    round_trip((0..16).map(|i| i as u8).collect::<Vec<_>>());
+    round_trip((0..16).map(|i| i as f32 * 0.5).collect::<Vec<_>>());
+    round_trip(
+        (0..16)
+            .map(|i| Vector3::new(i as f32, 0.5, -(i as f32)))
+            .collect::<Vec<_>>(),
+    );
+}}
+
+fn round_trip<T>(vec: Vec<T>)
+where
+    T: PoolElement + Copy + ToVariant + FromVariant + PartialEq + Debug,
+{
+    assert_eq!(Ok(&vec), Vec::<T>::from_variant(&vec.to_variant()).as_ref());
+    assert_eq!(
+        Ok(&vec),
+        as_pool_array::from_variant::<Vec<T>>(&as_pool_array::to_variant(&vec)).as_ref(),
+    );
+}
+
+// Takes a while, so it is only built with the `bench` feature
+#[cfg(feature = "bench")]
+mod bench {
+    use std::fmt::Debug;
+    use std::time::{Duration, Instant};
+
+    use gdnative::core_types::as_pool_array;
+    use gdnative::core_types::PoolElement;
+    use gdnative::prelude::*;
+
+    const LEN: usize = 1 << 16;
+    const ROUNDS: u32 = 10;
+
+    // Compares the default `VariantArray` representation of large numeric sequences with the
+    // `PoolArray` one of `as_pool_array`, and prints the time taken by each.
+    crate::godot_itest! { test_pool_conversions_bench {
+        let bytes: Vec<u8> = (0..LEN).map(|i| i as u8).collect();
+        bench("Vec<u8>", bytes);
+
+        let heights: Vec<f32> = (0..LEN).map(|i| i as f32 * 0.5).collect();
+        bench("Vec<f32>", heights);
+
+        let vertices: Vec<Vector3> = (0..LEN)
+            .map(|i| Vector3::new(i as f32, 0.5, -(i as f32)))
+            .collect();
+        bench("Vec<Vector3>", vertices);
+    }}
+
+    fn bench<T>(name: &str, vec: Vec<T>)
+    where
+        T: PoolElement + Copy + ToVariant + FromVariant + PartialEq + Debug,
+    {
+        let variant_array = measure(|| {
+            Vec::<T>::from_variant(&vec.to_variant()).expect("should convert from VariantArray")
+        });
+        let pool_array = measure(|| {
+            as_pool_array::from_variant::<Vec<T>>(&as_pool_array::to_variant(&vec))
+                .expect("should convert from PoolArray")
+        });
+
+        assert_eq!(vec, variant_array.0);
+        assert_eq!(vec, pool_array.0);
+
+        godot_print!(
+            "   -- {} ({} elements, round trip): VariantArray {:?}, PoolArray {:?}",
+            name,
+            vec.len(),
+            variant_array.1,
+            pool_array.1,
+        );
+    }
+
+    /// Returns the result of `f` and the average time it took over `ROUNDS` calls.
+    fn measure<R>(mut f: impl FnMut() -> R) -> (R, Duration) {
+        let start = Instant::now();
+        let mut result = f();
+        for _ in 1..ROUNDS {
+            result = f();
+        }
+        (result, start.elapsed() / ROUNDS)
+    }
+}
@@ -1,13 +1,38 @@
+use std::cell::Cell;
 use std::error::Error;
 use std::ops::Add;
 
-use gdnative::export::{StaticArgs, StaticArgsMethod, StaticallyNamed};
+use gdnative::api::{node, Camera2D};
+use gdnative::export::hint::{DictionaryHint, FlagsHint, IntHint, NodePathHint, RangeHint};
+use gdnative::export::{
+    DynamicProperties, DynamicProperty, LifecycleHooks, Notification, Signal, StaticArgs,
+    StaticArgsMethod, StaticallyNamed,
+};
 use gdnative::prelude::*;
 
 pub(crate) fn run_tests() -> bool {
     let mut status = true;
 
     status &= test_register_property();
+    status &= test_register_typed_signal();
+    status &= test_register_constants();
+    status &= test_register_property_hints();
+    status &= test_register_property_groups();
+    status &= test_register_nested_properties();
+    status &= test_register_instance_property();
+    status &= test_register_property_hooks();
+    status &= test_register_dynamic_properties();
+    status &= test_register_lifecycle_hooks();
+    status &= test_register_lifecycle_hooks_reference();
+    #[cfg(feature = "inventory")]
+    {
+        status &= test_register_auto();
+    }
+    status &= test_register_mixins();
+    status &= test_register_documentation();
+    status &= test_register_collect_classes();
+    status &= test_register_method_args();
+    status &= test_register_method_opt_default();
     status &= test_advanced_methods();
     status &= test_varargs_gets();
     status &= test_varargs_to_tuple();
@@ -18,9 +43,24 @@ pub(crate) fn run_tests() -> bool {
 pub(crate) fn register(handle: InitHandle) {
     handle.add_class::<RegisterSignal>();
     handle.add_class::<RegisterProperty>();
+    handle.add_class::<RegisterConstants>();
+    handle.add_class::<RegisterPropertyHints>();
+    handle.add_class::<RegisterPropertyGroups>();
+    handle.add_class::<RegisterNestedProperties>();
+    handle.add_class::<RegisterTypedResource>();
+    handle.add_class::<RegisterInstanceProperty>();
+    handle.add_class::<RegisterPropertyHooks>();
+    handle.add_class::<RegisterDynamicProperties>();
+    handle.add_class::<RegisterLifecycleHooks>();
+    handle.add_class::<RegisterReferenceLifecycleHooks>();
+    handle.add_class::<RegisterMixins>();
+    handle.add_class::<RegisterDocumented>();
+    handle.add_class::<RegisterMethodArgs>();
     handle.add_class::<AdvancedMethods>();
     handle.add_class::<VarargsGets>();
     handle.add_class::<VarargsToTuple>();
+    #[cfg(feature = "inventory")]
+    handle.add_all_classes();
 }
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -37,6 +77,12 @@ impl NativeClass for RegisterSignal {
             .signal("progress")
             .with_param("amount", VariantType::I64)
             .done();
+
+        builder
+            .signal("typed_progress")
+            .with_typed_param::<i64>("amount")
+            .with_typed_param::<Ref<Texture>>("icon")
+            .done();
     }
 }
 
@@ -97,6 +143,843 @@ crate::godot_itest! { test_register_property {
     assert_eq!(Some(4242), unsafe { base.call("get_value", &[]).to() });
 }}
 
+#[export_enum]
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum MachineState {
+    Idle,
+    Running = 10,
+}
+
+#[derive(NativeClass)]
+#[no_constructor]
+#[export_enums(MachineState)]
+#[register_with(Self::register)]
+struct RegisterConstants {
+    #[property]
+    state: MachineState,
+}
+
+#[methods]
+impl RegisterConstants {
+    fn register(builder: &ClassBuilder<Self>) {
+        builder.constant("MAX_SPEED", 300);
+    }
+
+    #[method]
+    fn set_state(&mut self, state: MachineState) {
+        self.state = state;
+    }
+
+    #[method]
+    fn get_state(&self) -> MachineState {
+        self.state
+    }
+}
+
+crate::godot_itest! { test_register_constants {
+    let obj = RegisterConstants { state: MachineState::Idle }.emplace();
+    let base = obj.into_base();
+
+    assert_eq!(Some(0), base.get("MACHINE_STATE_IDLE").to::<i64>());
+    assert_eq!(Some(10), base.get("MACHINE_STATE_RUNNING").to::<i64>());
+    assert_eq!(Some(300), base.get("MAX_SPEED").to::<i64>());
+
+    // Constants are read-only
+    base.set("MAX_SPEED", 1.to_variant());
+    assert_eq!(Some(300), base.get("MAX_SPEED").to::<i64>());
+
+    let running = base.get("MACHINE_STATE_RUNNING");
+    unsafe { base.call("set_state", &[running]) };
+    assert_eq!(Some(MachineState::Running), unsafe { base.call("get_state", &[]).to() });
+
+    // Unknown values are rejected
+    unsafe { base.call("set_state", &[5.to_variant()]) };
+    assert_eq!(Some(10), unsafe { base.call("get_state", &[]).to::<i64>() });
+
+    // Enum properties are exported as dropdowns with the discriminants of the variants
+    let state = base
+        .get_property_list()
+        .iter()
+        .filter_map(|prop| prop.to::<Dictionary>())
+        .find(|prop| prop.get("name").and_then(|name| name.to::<String>()).as_deref() == Some("state"))
+        .expect("state property should be registered");
+    assert_eq!(Some(VariantType::I64 as i64), state.get("type").and_then(|t| t.to::<i64>()));
+    assert_eq!(
+        Some(gdnative::sys::godot_property_hint_GODOT_PROPERTY_HINT_ENUM as i64),
+        state.get("hint").and_then(|hint| hint.to::<i64>()),
+    );
+    assert_eq!(
+        Some("Idle:0,Running:10".to_string()),
+        state.get("hint_string").and_then(|hint| hint.to::<String>()),
+    );
+
+    base.set("state", 0.to_variant());
+    assert_eq!(Some(MachineState::Idle), unsafe { base.call("get_state", &[]).to() });
+    base.set("state", 5.to_variant());
+    assert_eq!(Some(MachineState::Idle), unsafe { base.call("get_state", &[]).to() });
+}}
+
+#[derive(Debug)]
+enum Element {
+    Fire,
+    Water,
+    Earth,
+}
+
+impl Element {
+    fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            1 => Some(Element::Fire),
+            2 => Some(Element::Water),
+            8 => Some(Element::Earth),
+            _ => None,
+        }
+    }
+}
+
+#[derive(NativeClass)]
+#[no_constructor]
+struct RegisterPropertyHints {
+    #[property(hint = "FlagsHint::from_bitflags(Element::from_bits)")]
+    elements: i64,
+    #[property(hint = "IntHint::Layers2DPhysics")]
+    layers: i64,
+    #[property(hint = "NodePathHint::valid_types::<Node2D>().with_valid_type::<Camera2D>()")]
+    target: NodePath,
+    #[property(hint = "Self::speeds_hint")]
+    speeds: Dictionary,
+}
+
+impl RegisterPropertyHints {
+    fn speeds_hint() -> DictionaryHint {
+        DictionaryHint::with_value_hint::<i64>(IntHint::Range(RangeHint::new(0, 10)))
+    }
+}
+
+crate::godot_itest! { test_register_property_hints {
+    let obj = RegisterPropertyHints {
+        elements: 0,
+        layers: 0,
+        target: NodePath::default(),
+        speeds: Dictionary::new_shared(),
+    }
+    .emplace();
+    let base = obj.into_base();
+
+    let properties = base.get_property_list();
+    let hint_of = |name: &str| {
+        let property = properties
+            .iter()
+            .filter_map(|prop| prop.to::<Dictionary>())
+            .find(|prop| prop.get("name").and_then(|n| n.to::<String>()).as_deref() == Some(name))
+            .expect("property should be registered");
+        let hint = property.get("hint").and_then(|hint| hint.to::<i64>());
+        let hint_string = property.get("hint_string").and_then(|hint| hint.to::<String>());
+        (hint.expect("hint should be an integer"), hint_string.expect("hint_string should be a string"))
+    };
+
+    // Bits without a name are left empty, trailing ones are omitted
+    assert_eq!(
+        (gdnative::sys::godot_property_hint_GODOT_PROPERTY_HINT_FLAGS as i64, "Fire,Water,,Earth".to_string()),
+        hint_of("elements"),
+    );
+    assert_eq!(
+        (gdnative::sys::godot_property_hint_GODOT_PROPERTY_HINT_LAYERS_2D_PHYSICS as i64, String::new()),
+        hint_of("layers"),
+    );
+    // `PROPERTY_HINT_NODE_PATH_VALID_TYPES` in Godot's `PropertyHint` enum
+    assert_eq!((35, "Node2D,Camera2D".to_string()), hint_of("target"));
+    // Values are `int` (2) with a range hint (1)
+    assert_eq!(
+        (gdnative::sys::godot_property_hint_GODOT_PROPERTY_HINT_TYPE_STRING as i64, "2/1:0,10".to_string()),
+        hint_of("speeds"),
+    );
+}}
+
+#[derive(NativeClass)]
+#[no_constructor]
+#[register_with(Self::register)]
+struct RegisterPropertyGroups {
+    #[property(group = "Movement")]
+    speed: f32,
+    #[property]
+    health: i64,
+}
+
+impl RegisterPropertyGroups {
+    fn register(builder: &ClassBuilder<Self>) {
+        builder.property_category("Stats");
+        builder
+            .property::<i64>("stats_level")
+            .with_getter(|_, _| 1)
+            .done();
+    }
+}
+
+crate::godot_itest! { test_register_property_groups {
+    let obj = RegisterPropertyGroups { speed: 1.0, health: 10 }.emplace();
+    let base = obj.into_base();
+
+    let group = PropertyUsage::GROUP.bits() as i64;
+    let category = PropertyUsage::CATEGORY.bits() as i64;
+
+    // Script properties, with the header flags of their usage
+    let entries = base
+        .get_property_list()
+        .iter()
+        .filter_map(|prop| prop.to::<Dictionary>())
+        .filter_map(|prop| {
+            let name = prop.get("name")?.to::<String>()?;
+            let usage = prop.get("usage")?.to::<i64>()?;
+            Some((name, usage & (group | category)))
+        })
+        .skip_while(|(name, _)| name != "Movement")
+        .collect::<Vec<_>>();
+
+    let expected = [
+        ("Movement", group),
+        ("speed", 0),
+        ("", group),
+        ("health", 0),
+        ("Stats", category),
+        ("stats_level", 0),
+    ];
+    let entries = entries
+        .iter()
+        .take(expected.len())
+        .map(|(name, usage)| (name.as_str(), *usage))
+        .collect::<Vec<_>>();
+    assert_eq!(&expected[..], &entries[..]);
+}}
+
+#[derive(NestedProperties)]
+struct NestedMovement {
+    #[property]
+    speed: f64,
+    #[property(path = "jump_count")]
+    jumps: i64,
+}
+
+#[derive(NestedProperties)]
+struct NestedConfig {
+    #[property]
+    name: String,
+    #[property(nested)]
+    movement: NestedMovement,
+}
+
+#[derive(NativeClass)]
+#[no_constructor]
+struct RegisterNestedProperties {
+    #[property(nested)]
+    config: NestedConfig,
+}
+
+crate::godot_itest! { test_register_nested_properties {
+    let obj = RegisterNestedProperties {
+        config: NestedConfig {
+            name: "foo".to_string(),
+            movement: NestedMovement { speed: 1.0, jumps: 2 },
+        },
+    }
+    .emplace()
+    .into_shared();
+    let obj = unsafe { obj.assume_safe() };
+    let base = obj.base();
+
+    let names = base
+        .get_property_list()
+        .iter()
+        .filter_map(|prop| prop.to::<Dictionary>())
+        .filter_map(|prop| prop.get("name")?.to::<String>())
+        .filter(|name| name.starts_with("config/"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec!["config/name", "config/movement/speed", "config/movement/jump_count"],
+        names,
+    );
+
+    assert_eq!(Some("foo".to_string()), base.get("config/name").to());
+    assert_eq!(Some(2), base.get("config/movement/jump_count").to::<i64>());
+
+    base.set("config/name", "bar");
+    base.set("config/movement/speed", 3.5);
+
+    obj.map(|script, _| {
+        assert_eq!("bar", script.config.name);
+        assert!((script.config.movement.speed - 3.5).abs() < f64::EPSILON);
+    })
+    .unwrap();
+}}
+
+#[derive(NativeClass)]
+#[inherit(Resource)]
+#[no_constructor]
+struct RegisterTypedResource {
+    value: i64,
+}
+
+#[derive(NativeClass)]
+#[no_constructor]
+struct RegisterInstanceProperty {
+    #[property]
+    resource: Option<Instance<RegisterTypedResource>>,
+}
+
+crate::godot_itest! { test_register_instance_property {
+    let obj = RegisterInstanceProperty { resource: None }.emplace().into_shared();
+    let obj = unsafe { obj.assume_safe() };
+    let base = obj.base();
+
+    // The editor only offers resources with the script of the class
+    let property = base
+        .get_property_list()
+        .iter()
+        .filter_map(|prop| prop.to::<Dictionary>())
+        .find(|prop| prop.get("name").and_then(|name| name.to::<String>()).as_deref() == Some("resource"))
+        .expect("resource property should be registered");
+    assert_eq!(
+        Some(gdnative::sys::godot_property_hint_GODOT_PROPERTY_HINT_RESOURCE_TYPE as i64),
+        property.get("hint").and_then(|hint| hint.to::<i64>()),
+    );
+    assert_eq!(
+        Some("RegisterTypedResource".to_string()),
+        property.get("hint_string").and_then(|hint| hint.to::<String>()),
+    );
+
+    let resource = RegisterTypedResource { value: 42 }.emplace().into_shared();
+    base.set("resource", resource);
+
+    // Resources without the script are rejected
+    base.set("resource", gdnative::api::Resource::new().into_shared());
+
+    obj.map(|script, _| {
+        let resource = script.resource.as_ref().expect("typed resource should be assigned");
+        let value = unsafe { resource.assume_safe() }
+            .map(|resource, _| resource.value)
+            .unwrap();
+        assert_eq!(42, value);
+    })
+    .unwrap();
+}}
+
+#[derive(NativeClass)]
+#[no_constructor]
+#[register_with(Self::register)]
+struct RegisterPropertyHooks {
+    #[property(notify = "health_changed")]
+    health: i64,
+    level: i64,
+    health_changes: Vec<i64>,
+}
+
+#[methods]
+impl RegisterPropertyHooks {
+    fn register(builder: &ClassBuilder<Self>) {
+        builder
+            .signal("health_changed")
+            .with_typed_param::<i64>("health")
+            .done();
+
+        builder
+            .property::<i64>("level")
+            .with_getter(|this, _| this.level)
+            .with_setter(|this, _, level| this.level = level)
+            .with_validator(|level| {
+                if level < 0 {
+                    Err(format!("level must not be negative, got {}", level))
+                } else {
+                    Ok(level.min(10))
+                }
+            })
+            .done();
+    }
+
+    #[method]
+    fn on_health_changed(&mut self, health: i64) {
+        self.health_changes.push(health);
+    }
+}
+
+crate::godot_itest! { test_register_property_hooks {
+    let obj = RegisterPropertyHooks {
+        health: 10,
+        level: 1,
+        health_changes: Vec::new(),
+    }
+    .emplace()
+    .into_shared();
+    let obj = unsafe { obj.assume_safe() };
+    let base = obj.base();
+
+    base.connect(
+        "health_changed",
+        base,
+        "on_health_changed",
+        VariantArray::new_shared(),
+        0,
+    )
+    .unwrap();
+
+    base.set("health", 20);
+    base.set("health", 20);
+    base.set("health", 5);
+
+    base.set("level", 5);
+    base.set("level", -1);
+    assert_eq!(Some(5), base.get("level").to::<i64>());
+    base.set("level", 50);
+    assert_eq!(Some(10), base.get("level").to::<i64>());
+
+    obj.map(|script, _| {
+        assert_eq!(vec![20, 5], script.health_changes);
+    })
+    .unwrap();
+}}
+
+#[derive(NativeClass)]
+#[no_constructor]
+#[register_with(Self::register)]
+struct RegisterDynamicProperties {
+    slots: Vec<i64>,
+}
+
+impl RegisterDynamicProperties {
+    fn register(builder: &ClassBuilder<Self>) {
+        builder.dynamic_properties();
+    }
+
+    fn slot_index(name: &str) -> Option<usize> {
+        name.strip_prefix("slot_")?.parse().ok()
+    }
+}
+
+impl DynamicProperties for RegisterDynamicProperties {
+    fn property_list(&self, _owner: TRef<Reference>) -> Vec<DynamicProperty> {
+        (0..self.slots.len())
+            .map(|i| DynamicProperty::typed::<i64>(format!("slot_{}", i), None))
+            .collect()
+    }
+
+    fn get_property(&self, _owner: TRef<Reference>, name: &str) -> Option<Variant> {
+        let index = Self::slot_index(name)?;
+        self.slots.get(index).map(|slot| slot.to_variant())
+    }
+
+    fn set_property(&mut self, _owner: TRef<Reference>, name: &str, value: Variant) -> bool {
+        let slot = Self::slot_index(name).and_then(|index| self.slots.get_mut(index));
+        match (slot, value.to::<i64>()) {
+            (Some(slot), Some(value)) => {
+                *slot = value;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+crate::godot_itest! { test_register_dynamic_properties {
+    let obj = RegisterDynamicProperties { slots: vec![1, 2] }
+        .emplace()
+        .into_shared();
+    let obj = unsafe { obj.assume_safe() };
+    let base = obj.base();
+
+    let names = base
+        .get_property_list()
+        .iter()
+        .filter_map(|prop| prop.to::<Dictionary>())
+        .filter_map(|prop| prop.get("name")?.to::<String>())
+        .filter(|name| name.starts_with("slot_"))
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["slot_0", "slot_1"], names);
+
+    assert_eq!(Some(2), base.get("slot_1").to::<i64>());
+    assert!(base.get("slot_2").is_nil());
+
+    base.set("slot_0", 10);
+    base.set("slot_2", 20);
+    obj.map(|script, _| assert_eq!(vec![10, 2], script.slots))
+        .unwrap();
+}}
+
+thread_local! {
+    static LIFECYCLE_PREDELETED: Cell<bool> = Cell::new(false);
+}
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+#[lifecycle_hooks]
+#[no_constructor]
+struct RegisterLifecycleHooks {
+    ready: u32,
+    notifications: Vec<Notification>,
+}
+
+#[methods]
+impl RegisterLifecycleHooks {}
+
+impl LifecycleHooks for RegisterLifecycleHooks {
+    fn on_ready(&mut self, _owner: TRef<Node>) {
+        self.ready += 1;
+    }
+
+    fn on_predelete(&mut self, owner: TRef<Node>) {
+        assert_eq!("lifecycle", owner.name().to_string());
+        LIFECYCLE_PREDELETED.with(|predeleted| predeleted.set(true));
+    }
+
+    fn on_notification(&mut self, _owner: TRef<Node>, what: Notification) {
+        self.notifications.push(what);
+    }
+}
+
+crate::godot_itest! { test_register_lifecycle_hooks {
+    let obj = RegisterLifecycleHooks { ready: 0, notifications: Vec::new() }
+        .emplace()
+        .into_shared();
+    let obj = unsafe { obj.assume_safe() };
+    let base = obj.base();
+    base.set_name("lifecycle");
+
+    base.notification(Node::NOTIFICATION_READY, false);
+    base.notification(Node::NOTIFICATION_PAUSED, false);
+    obj.map(|script, _| {
+        assert_eq!(1, script.ready);
+        assert!(script.notifications.ends_with(&[
+            node::Notification::READY.into(),
+            node::Notification::PAUSED.into(),
+        ]));
+        assert!(script.notifications.last().unwrap() == &node::Notification::PAUSED);
+    })
+    .unwrap();
+
+    LIFECYCLE_PREDELETED.with(|predeleted| predeleted.set(false));
+    unsafe { base.assume_unique().free() };
+    assert!(LIFECYCLE_PREDELETED.with(Cell::get));
+}}
+
+#[derive(NativeClass)]
+#[lifecycle_hooks]
+#[no_constructor]
+struct RegisterReferenceLifecycleHooks {
+    ready: u32,
+    notifications: Vec<i64>,
+}
+
+#[methods]
+impl RegisterReferenceLifecycleHooks {}
+
+impl LifecycleHooks for RegisterReferenceLifecycleHooks {
+    fn on_ready(&mut self, _owner: TRef<Reference>) {
+        self.ready += 1;
+    }
+
+    fn on_notification(&mut self, _owner: TRef<Reference>, what: Notification) {
+        self.notifications.push(what.into());
+    }
+}
+
+crate::godot_itest! { test_register_lifecycle_hooks_reference {
+    let obj = RegisterReferenceLifecycleHooks { ready: 0, notifications: Vec::new() }
+        .emplace()
+        .into_shared();
+    let obj = unsafe { obj.assume_safe() };
+
+    // Only nodes are ready, the value is passed on as is for other classes
+    obj.base().notification(Node::NOTIFICATION_READY, false);
+    obj.map(|script, _| {
+        assert_eq!(0, script.ready);
+        assert!(script.notifications.ends_with(&[Node::NOTIFICATION_READY]));
+    })
+    .unwrap();
+}}
+
+#[cfg(feature = "inventory")]
+mod auto_register {
+    use gdnative::prelude::*;
+
+    #[derive(NativeClass)]
+    #[auto_register]
+    struct RegisterAuto;
+
+    #[methods]
+    impl RegisterAuto {
+        fn new(_base: &Reference) -> Self {
+            RegisterAuto
+        }
+
+        #[method]
+        fn answer(&self) -> i64 {
+            42
+        }
+    }
+
+    // Each class exports the other one, so one of them is registered before the class of its
+    // property, in whichever order they are registered
+    #[derive(NativeClass)]
+    #[inherit(Resource)]
+    #[auto_register]
+    #[no_constructor]
+    struct RegisterAutoFirst {
+        #[property]
+        second: Option<Instance<RegisterAutoSecond>>,
+    }
+
+    #[derive(NativeClass)]
+    #[inherit(Resource)]
+    #[auto_register]
+    #[no_constructor]
+    struct RegisterAutoSecond {
+        #[property]
+        first: Option<Instance<RegisterAutoFirst>>,
+    }
+
+    crate::godot_itest! { test_register_auto {
+        let base = RegisterAuto::new_instance().into_base();
+        assert_eq!(Some(42), unsafe { base.call("answer", &[]).to::<i64>() });
+
+        let first = RegisterAutoFirst { second: None }.emplace().into_base();
+        let second = RegisterAutoSecond { first: None }.emplace().into_base();
+        for (base, property, class_name) in [
+            (first, "second", "RegisterAutoSecond"),
+            (second, "first", "RegisterAutoFirst"),
+        ] {
+            let property = base
+                .get_property_list()
+                .iter()
+                .filter_map(|prop| prop.to::<Dictionary>())
+                .find(|prop| prop.get("name").and_then(|name| name.to::<String>()).as_deref() == Some(property))
+                .expect("instance property should be registered");
+            assert_eq!(
+                Some(class_name.to_string()),
+                property.get("hint_string").and_then(|hint| hint.to::<String>()),
+            );
+        }
+    }}
+}
+#[cfg(feature = "inventory")]
+use auto_register::test_register_auto;
+
+mod mixin_traits {
+    pub trait Damageable {
+        fn take_damage(&mut self, amount: i64) -> i64;
+    }
+}
+
+#[derive(NativeClass)]
+#[mixins(RegisterMixinsDamageable, RegisterMixinsExtra)]
+struct RegisterMixins {
+    health: i64,
+}
+
+#[methods]
+impl RegisterMixins {
+    fn new(_base: &Reference) -> Self {
+        RegisterMixins { health: 100 }
+    }
+
+    #[method]
+    fn health(&self) -> i64 {
+        self.health
+    }
+}
+
+#[methods(mixin = "RegisterMixinsDamageable")]
+impl mixin_traits::Damageable for RegisterMixins {
+    #[method]
+    fn take_damage(&mut self, amount: i64) -> i64 {
+        self.health -= amount;
+        self.health
+    }
+}
+
+#[methods(mixin = "RegisterMixinsExtra")]
+impl RegisterMixins {
+    #[method]
+    fn heal(&mut self, amount: i64) {
+        self.health += amount;
+    }
+}
+
+crate::godot_itest! { test_register_mixins {
+    let base = RegisterMixins::new_instance().into_base();
+
+    assert_eq!(Some(70), unsafe { base.call("take_damage", &[Variant::new(30)]).to::<i64>() });
+    unsafe { base.call("heal", &[Variant::new(5)]) };
+    assert_eq!(Some(75), unsafe { base.call("health", &[]).to::<i64>() });
+}}
+
+/// A documented class.
+///
+/// With a longer description.
+#[derive(NativeClass)]
+#[no_constructor]
+struct RegisterDocumented {
+    /// Health of the class.
+    #[property(default = 100)]
+    health: i64,
+    /// Emitted when <hit>.
+    #[signal]
+    hit: Signal<(i64,)>,
+}
+
+#[methods]
+impl RegisterDocumented {
+    /// Heals by `amount`.
+    #[method]
+    fn heal(&mut self, amount: i64) {
+        self.health += amount;
+    }
+}
+
+crate::godot_itest! { test_register_documentation {
+    let xml = gdnative::export::class_reference_xml::<RegisterDocumented>()
+        .expect("class should be registered");
+
+    assert!(xml.contains(r#"<class name="RegisterDocumented" inherits="Reference">"#));
+    assert!(xml.contains("<brief_description>\n\t\tA documented class.\n\t</brief_description>"));
+    assert!(xml.contains("With a longer description."));
+    assert!(xml.contains(r#"<member name="health" type="int" default="100">"#));
+    assert!(xml.contains("Health of the class."));
+    assert!(xml.contains(r#"<method name="heal">"#));
+    assert!(xml.contains(r#"<argument index="0" name="amount" type="int" />"#));
+    assert!(xml.contains("Heals by `amount`."));
+    assert!(xml.contains(r#"<signal name="hit">"#));
+    assert!(xml.contains("Emitted when &lt;hit&gt;."));
+}}
+
+crate::godot_itest! { test_register_collect_classes {
+    use gdnative::init::{RegisteredClass, ScriptResources};
+
+    let classes = InitHandle::collect_classes(|handle| {
+        handle.add_class::<RegisterDocumented>();
+        handle.add_tool_class_as::<RegisterMethodArgs>("ToolMethodArgs".to_string());
+    });
+    assert_eq!(
+        vec![
+            RegisteredClass {
+                name: "RegisterDocumented".to_string(),
+                base: "Reference",
+                is_tool: false,
+            },
+            RegisteredClass {
+                name: "ToolMethodArgs".to_string(),
+                base: "Reference",
+                is_tool: true,
+            },
+        ],
+        classes,
+    );
+
+    let resources = ScriptResources::new("res://test.gdnlib", "res://scripts", classes.clone());
+    assert_eq!(
+        "res://scripts/ToolMethodArgs.gdns",
+        resources.script_path(&classes[1]),
+    );
+}}
+
+#[derive(NativeClass)]
+#[no_constructor]
+struct RegisterMethodArgs;
+
+#[methods]
+impl RegisterMethodArgs {
+    #[method]
+    fn spawn(&self, #[base] _base: &Reference, name: GodotString, _count: i64, extra: Variant) {
+        let _ = (name, extra);
+    }
+
+    #[method]
+    fn scale(&self, value: i64, #[opt(default = 10)] factor: i64, #[opt] offset: i64) -> i64 {
+        value * factor + offset
+    }
+}
+
+crate::godot_itest! { test_register_method_args {
+    let obj = RegisterMethodArgs.emplace();
+    let base = obj.into_base();
+
+    let method = base
+        .get_method_list()
+        .iter()
+        .filter_map(|method| method.to::<Dictionary>())
+        .find(|method| {
+            let name = method.get("name").and_then(|name| name.to::<String>());
+            name.as_deref() == Some("spawn")
+        })
+        .expect("method should be registered");
+
+    let args = method
+        .get("args")
+        .and_then(|args| args.to::<VariantArray>())
+        .expect("method should have arguments")
+        .iter()
+        .filter_map(|arg| arg.to::<Dictionary>())
+        .filter_map(|arg| {
+            let name = arg.get("name")?.to::<String>()?;
+            let ty = arg.get("type")?.to::<i64>()?;
+            Some((name, ty))
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            ("name".to_string(), VariantType::GodotString as i64),
+            ("count".to_string(), VariantType::I64 as i64),
+            ("extra".to_string(), VariantType::Nil as i64),
+        ],
+        args
+    );
+}}
+
+crate::godot_itest! { test_register_method_opt_default {
+    let obj = RegisterMethodArgs.emplace();
+    let base = obj.into_base();
+
+    let scale = |args: &[Variant]| unsafe { base.call("scale", args).to::<i64>() };
+    assert_eq!(Some(20), scale(&[2.to_variant()]));
+    assert_eq!(Some(6), scale(&[2.to_variant(), 3.to_variant()]));
+    assert_eq!(Some(7), scale(&[2.to_variant(), 3.to_variant(), 1.to_variant()]));
+}}
+
+crate::godot_itest! { test_register_typed_signal {
+    let obj = RegisterSignal::new_instance();
+    let base = obj.into_base();
+
+    let signal = base
+        .get_signal_list()
+        .iter()
+        .filter_map(|signal| signal.to::<Dictionary>())
+        .find(|signal| {
+            let name = signal.get("name").and_then(|name| name.to::<String>());
+            name.as_deref() == Some("typed_progress")
+        })
+        .expect("signal should be registered");
+
+    let args = signal
+        .get("args")
+        .and_then(|args| args.to::<VariantArray>())
+        .expect("signal should have arguments");
+    assert_eq!(2, args.len());
+
+    let amount = args.get(0).to::<Dictionary>().unwrap();
+    assert_eq!(
+        Some(VariantType::I64 as i64),
+        amount.get("type").and_then(|t| t.to::<i64>())
+    );
+
+    let icon = args.get(1).to::<Dictionary>().unwrap();
+    assert_eq!(
+        Some(VariantType::Object as i64),
+        icon.get("type").and_then(|t| t.to::<i64>())
+    );
+    assert_eq!(
+        Some("Texture".to_string()),
+        icon.get("hint_string").and_then(|h| h.to::<String>())
+    );
+}}
+
 #[derive(NativeClass)]
 #[inherit(Reference)]
 #[register_with(register_methods)]
@@ -226,6 +1109,7 @@ impl VarargsGets {
 
     fn register(builder: &ClassBuilder<VarargsGets>) {
         builder.method("calc", CalcMethod).done();
+        builder.method("calc_read", CalcReadMethod).done();
     }
 }
 
@@ -250,6 +1134,25 @@ impl Method<VarargsGets> for CalcMethod {
     }
 }
 
+struct CalcReadMethod;
+
+impl Method<VarargsGets> for CalcReadMethod {
+    fn call(
+        &self,
+        _this: TInstance<'_, VarargsGets>,
+        mut args: gdnative::export::Varargs<'_>,
+    ) -> Variant {
+        let a = args.read::<i64>().with_name("a").get();
+        let b = args.read::<i64>().with_name("b").get();
+        let c = args.read::<i64>().with_name("c").with_default(11).get();
+
+        match (a, b, c) {
+            (Ok(a), Ok(b), Ok(c)) => (a * b - c).to_variant(),
+            _ => Variant::nil(),
+        }
+    }
+}
+
 crate::godot_itest! { test_varargs_gets {
     let thing = Instance::<VarargsGets, _>::new();
     let base = thing.base();
@@ -259,6 +1162,15 @@ crate::godot_itest! { test_varargs_gets {
 
     let args = [3_i64.to_variant(), 4_i64.to_variant()];
     assert_eq!(unsafe { base.call("calc", &args).to() }, Some(1));
+
+    let args = [3_i64.to_variant(), 4_i64.to_variant(), 5_i64.to_variant()];
+    assert_eq!(unsafe { base.call("calc_read", &args).to() }, Some(7));
+
+    let args = [3_i64.to_variant(), 4_i64.to_variant()];
+    assert_eq!(unsafe { base.call("calc_read", &args).to() }, Some(1));
+
+    let args = [3_i64.to_variant()];
+    assert!(unsafe { base.call("calc_read", &args).is_nil() });
 }}
 
 #[derive(NativeClass)]